@@ -2,12 +2,44 @@
  * UI Bridge SWC Plugin Configuration
  */
 export interface UIBridgeSwcPluginConfig {
+  /**
+   * Whether to instrument at all. When false, files are returned unchanged.
+   * @default true
+   */
+  enabled?: boolean;
+
   /**
    * Elements to automatically instrument
    * @default ['button', 'input', 'select', 'textarea', 'a', 'form']
    */
   elements?: string[];
 
+  /**
+   * Extra elements to instrument in addition to `elements` (keeps the defaults)
+   * @default []
+   */
+  additionalElements?: string[];
+
+  /**
+   * Also instrument any element with a truthy `contenteditable`, even if not in `elements`
+   * @default false
+   */
+  detectContenteditable?: boolean;
+
+  /**
+   * Which tags count as HTML elements rather than components: any lowercase tag,
+   * only tags in `htmlElements`, or lowercase tags without a dash (dashed tags like
+   * `<my-widget>` are custom elements and left alone)
+   * @default 'lowercase_first'
+   */
+  htmlElementPredicate?: 'lowercase_first' | 'explicit_list' | 'contains_dash_is_custom';
+
+  /**
+   * Tags counted as HTML elements with `htmlElementPredicate: 'explicit_list'`
+   * @default []
+   */
+  htmlElements?: string[];
+
   /**
    * Prefix for generated IDs
    * @default 'ui'
@@ -15,7 +47,29 @@ export interface UIBridgeSwcPluginConfig {
   idPrefix?: string;
 
   /**
-   * Attribute name for the generated ID
+   * ID prefixes by component name (e.g. `{ CheckoutForm: 'checkout' }`),
+   * overriding `idPrefix` for elements rendered in that component
+   * @default {}
+   */
+  componentPrefixes?: Record<string, string>;
+
+  /**
+   * Separator between the parts of an ID (prefix, component, descriptor, type)
+   * @default '-'
+   */
+  idSeparator?: string;
+
+  /**
+   * Separator between the words within a part, e.g. `'_'` gives
+   * `ui-login_form-sign_in-button` so the parts can be told apart
+   * @default '-'
+   */
+  wordSeparator?: string;
+
+  /**
+   * Attribute name for the generated ID. Like every attribute name option, it may use
+   * another namespace than `data-` (`x-ui-id`, or `ui:id` for a namespaced attribute, which
+   * needs the JSX transform's `throwIfNamespace: false`), but not a standard HTML attribute.
    * @default 'data-ui-id'
    */
   idAttribute?: string;
@@ -32,6 +86,179 @@ export interface UIBridgeSwcPluginConfig {
    */
   typeAttribute?: string;
 
+  /**
+   * Emit separate `data-ui-id`/`data-ui-type`/`data-ui-aliases` attributes, or a single
+   * combined `data-ui="id|type|alias1,alias2"` attribute
+   * @default 'separate'
+   */
+  emitMode?: 'separate' | 'compact';
+
+  /**
+   * Attribute name for the combined value in compact mode
+   * @default 'data-ui'
+   */
+  compactAttribute?: string;
+
+  /**
+   * Delimiter between id, type and aliases in compact mode
+   * @default '|'
+   */
+  compactDelimiter?: string;
+
+  /**
+   * The build being instrumented, so one config serves both. `'development'` emits every
+   * attribute (text, role, confidence, ...), `'production'` only those in
+   * `productionAttributes` that the other options enable. Unset emits what the other
+   * options ask for.
+   */
+  environment?: 'development' | 'production';
+
+  /**
+   * The attributes emitted, at most, when `environment` is `'production'`
+   * @default ['id']
+   */
+  productionAttributes?: Array<
+    | 'id'
+    | 'type'
+    | 'aliases'
+    | 'role'
+    | 'text'
+    | 'group'
+    | 'primary'
+    | 'portal'
+    | 'confidence'
+    | 'index_key'
+  >;
+
+  /**
+   * Also set the element's `id` to the generated ID, so `document.getElementById` finds
+   * it. An existing `id` is never replaced, and one passed in a spread still wins. Items
+   * rendered by `.map()` get the callback's index appended (`"ui-rows-edit-button-" + i`),
+   * or no `id` when the callback takes no index.
+   * @default false
+   */
+  mirrorToId?: boolean;
+
+  /**
+   * Attribute name for the element's ARIA role
+   * @default 'data-ui-role'
+   */
+  roleAttribute?: string;
+
+  /**
+   * Emit the explicit `role` or the tag's implicit ARIA role (e.g. `a[href]` → `link`)
+   * @default false
+   */
+  emitRoleAttribute?: boolean;
+
+  /**
+   * Attribute name for the element's extracted text
+   * @default 'data-ui-text'
+   */
+  textAttribute?: string;
+
+  /**
+   * Emit the text the ID was derived from (after `textJoinStrategy`, captions and wrapping
+   * labels, before normalization), to trace an ID back to its source
+   * @default false
+   */
+  emitTextAttribute?: boolean;
+
+  /**
+   * Attribute name for the `.map()` index hint
+   * @default 'data-ui-index-key'
+   */
+  indexKeyAttribute?: string;
+
+  /**
+   * Expose the `.map()` callback index (`data-ui-index-key={i}`) on list items that have
+   * no static descriptor, so otherwise identical items can be told apart
+   * @default false
+   */
+  emitIndexKey?: boolean;
+
+  /**
+   * Attribute name for the ID confidence tier
+   * @default 'data-ui-confidence'
+   */
+  confidenceAttribute?: string;
+
+  /**
+   * Emit how reliable each ID is as a selector: `high` (label, id or `data-testid`),
+   * `medium` (static text or attributes) or `low` (fallback, positional or hashed).
+   * @default false
+   */
+  emitConfidence?: boolean;
+
+  /**
+   * Attribute name for the primary action marker
+   * @default 'data-ui-primary'
+   */
+  primaryAttribute?: string;
+
+  /**
+   * Mark each form's primary action (its default submit button: the first `type="submit"`
+   * input or button, or button without a type) with `data-ui-primary="true"`
+   * @default false
+   */
+  markPrimaryActions?: boolean;
+
+  /**
+   * Attribute name for the portal marker
+   * @default 'data-ui-portal'
+   */
+  portalAttribute?: string;
+
+  /**
+   * Mark elements rendered through `createPortal` / `ReactDOM.createPortal` with
+   * `portalAttribute`, since they render outside their component's DOM subtree
+   * @default false
+   */
+  portalComponentHint?: boolean;
+
+  /**
+   * Attribute name for a radio button's group. Radios get their shared `name` as
+   * their group and their `value` as their descriptor
+   * @default 'data-ui-group'
+   */
+  groupAttribute?: string;
+
+  /**
+   * Attribute holding an explicit descriptor (e.g. `data-ui-label="Primary Action"`), used before
+   * any inferred one for the ID and as the primary alias; stripped from output
+   * @default 'data-ui-label'
+   */
+  labelAttribute?: string;
+
+  /**
+   * Attribute that opts an element out of instrumentation. Its value is a comma-separated set
+   * of `id`, `type`, `aliases` or `all` (e.g. `data-ui-ignore="aliases"`); a bare attribute
+   * ignores everything. It's kept in the output, so instrumenting again leaves the element alone.
+   * @default 'data-ui-ignore'
+   */
+  ignoreAttribute?: string;
+
+  /**
+   * How separate text nodes (e.g. `Save<br/>Draft`) combine into the descriptor:
+   * all of them space-separated, only the first, or the longest
+   * @default 'concat'
+   */
+  textJoinStrategy?: 'concat' | 'first' | 'longest';
+
+  /**
+   * Use a descendant's `aria-label` (or `title`) as the descriptor when an element has no text,
+   * e.g. `<button><span aria-label="Close dialog" /></button>`
+   * @default false
+   */
+  deriveFromChildAria?: boolean;
+
+  /**
+   * Use the text of a static `dangerouslySetInnerHTML={{ __html: "..." }}`, with its tags
+   * stripped, when an element has no text children
+   * @default false
+   */
+  extractInnerHtml?: boolean;
+
   /**
    * Generate aliases from text content
    * @default true
@@ -44,6 +271,20 @@ export interface UIBridgeSwcPluginConfig {
    */
   includeComponentName?: boolean;
 
+  /**
+   * Name JSX passed as children or props to another component (`<Card><button /></Card>`)
+   * after the component it is written in. When false, it is named after the component
+   * it is passed to, as if written inside it.
+   * @default true
+   */
+  childrenInheritParentComponent?: boolean;
+
+  /**
+   * Maximum number of nested components tracked. JSX in components nested deeper is named
+   * and indexed as part of the innermost tracked component. Unset tracks every level.
+   */
+  maxComponentDepth?: number;
+
   /**
    * Include file path in generated ID
    * @default false
@@ -51,11 +292,90 @@ export interface UIBridgeSwcPluginConfig {
   includeFilePath?: boolean;
 
   /**
-   * Hash the generated ID for shorter strings
+   * Hash the generated ID for shorter strings (`<idPrefix>-xxxxxxxx`)
    * @default false
    */
   hashIds?: boolean;
 
+  /**
+   * Salt collision suffixes with a short, stable hash of the file's path relative to the
+   * project (`ui-login-form-sign-in-button-2-1a2b3c`), so they don't repeat across files.
+   * IDs without a suffix stay as they are; with `hashIds` the path is hashed into every ID.
+   * @default false
+   */
+  guaranteeGlobalUnique?: boolean;
+
+  /**
+   * IDs never emitted; an element that would get one is suffixed with its index
+   * @default []
+   */
+  reservedIds?: string[];
+
+  /**
+   * Maximum number of descriptor words kept in generated IDs
+   * @default 4
+   */
+  idMaxWords?: number;
+
+  /**
+   * Which words are kept when a descriptor exceeds `idMaxWords`.
+   * `head` keeps the first words; `head_tail` keeps the first words plus the last one.
+   * @default 'head'
+   */
+  truncationStrategy?: 'head' | 'head_tail';
+
+  /**
+   * Strip call-to-action prefixes from ID descriptors, so "Click to Save" gives `save`.
+   * Built in: "click here to", "tap here to", "click to", "tap to", "press to", "touch to".
+   * Text that is only a prefix is kept.
+   * @default false
+   */
+  stripCallToActionPrefixes?: boolean;
+
+  /**
+   * Prefixes stripped with `stripCallToActionPrefixes`, on top of the built-in ones
+   * @default []
+   */
+  callToActionPrefixes?: string[];
+
+  /**
+   * Maximum number of words kept per alias (unlimited when unset)
+   */
+  aliasMaxWords?: number;
+
+  /**
+   * How IDs are derived: `'semantic'` uses component/file context, `'content'` uses only
+   * the tag and descriptor so identical markup gets the same ID in any component
+   * @default 'semantic'
+   */
+  idStrategy?: 'semantic' | 'content';
+
+  /**
+   * Append the element index to content-addressed IDs
+   * @default false
+   */
+  contentIdIncludeIndex?: boolean;
+
+  /**
+   * Number that element indices in IDs (collision suffixes, `contentIdIncludeIndex`) start from
+   * @default 1
+   */
+  indexBase?: 0 | 1;
+
+  /**
+   * Zero-pad element indices in IDs to this many digits, e.g. `2` for `-01`, so selectors sort
+   * lexicographically
+   * @default 0
+   */
+  indexPadWidth?: number;
+
+  /**
+   * Generate IDs, types and aliases exactly like `@qontinui/ui-bridge-babel-plugin`,
+   * so selectors saved against it keep matching (see "Migrating from Babel Plugin")
+   * @default false
+   */
+  legacyBabelCompat?: boolean;
+
   /**
    * Maximum alias count per element
    * @default 5
@@ -63,11 +383,150 @@ export interface UIBridgeSwcPluginConfig {
   maxAliases?: number;
 
   /**
-   * Skip elements that already have data-ui-id
+   * Maximum byte length of the comma-joined aliases (unlimited when unset). Whole
+   * aliases are dropped from the end to fit, and the attribute is left out if none fit
+   */
+  maxAliasesBytes?: number;
+
+  /**
+   * Sort aliases lexicographically (before `maxAliases` truncation) so the output
+   * doesn't depend on source attribute order
+   * @default false
+   */
+  sortAliases?: boolean;
+
+  /**
+   * Use the nearest ancestor `title` as a descriptor for elements that have none
+   * (e.g. `<span title="Delete"><button>🗑</button></span>`)
+   * @default false
+   */
+  inheritAncestorTitle?: boolean;
+
+  /**
+   * Describe a `<button type="submit">` (or `"reset"`) without text by its type, giving
+   * e.g. `ui-form-submit-button` typed `submit-button`
+   * @default false
+   */
+  describeButtonType?: boolean;
+
+  /**
+   * Type a `<button>` with `aria-haspopup` by its popup (`menu-trigger`, `listbox-trigger`
+   * or `dialog-trigger`), or with `aria-controls` as `disclosure`
+   * @default false
+   */
+  detectTriggers?: boolean;
+
+  /**
+   * Whether elements are instrumented after (`'bottom_up'`) or before (`'top_down'`) their
+   * children. Text is always read from the original children, so this only affects which of a
+   * parent and its descendants claims a colliding ID first, and whether the ID map records each
+   * element's parent ID (top-down only).
+   * @default 'bottom_up'
+   */
+  traversalOrder?: 'bottom_up' | 'top_down';
+
+  /**
+   * Leave `<a>` elements without an `href` uninstrumented. Otherwise they are typed `text`
+   * rather than `link`, since they aren't focusable or interactive.
+   * @default false
+   */
+  skipAnchorsWithoutHref?: boolean;
+
+  /**
+   * Only instrument the outermost of nested instrumentable elements that share a descriptor,
+   * e.g. the `<a>` in `<a><button>Go</button></a>`
+   * @default false
+   */
+  dedupeNestedInteractive?: boolean;
+
+  /**
+   * How many levels of synonym groups to follow transitively (1 = direct synonyms only)
+   * @default 1
+   */
+  synonymDepth?: number;
+
+  /**
+   * Directory of `<locale>.json` synonym files, each a list of
+   * `{ "triggers": string[], "synonyms": string[] }` groups checked before the built-in ones.
+   * Only read by builds with the `native` feature, since the WASM sandbox may not allow file access.
+   */
+  synonymDir?: string;
+
+  /**
+   * Locale whose file in `synonymDir` is used (e.g. `'de'`; `'de-AT'` falls back to `'de'`)
+   */
+  locale?: string;
+
+  /**
+   * File that receives `export type UiId = "..." | ...`, a union of every generated ID in
+   * the build, for type-checked selectors. Each source file's IDs are kept in a directory
+   * next to it (`.ui-ids.d.ts-ids/` for `ui-ids.d.ts`), from which the union is rebuilt, so
+   * files transformed in parallel all end up in it. Only written by builds with the `native`
+   * feature.
+   */
+  typesOutputPath?: string;
+
+  /**
+   * Directory that receives a JSON map of each generated ID to its `file`, `line` and
+   * `component`, one fragment per source file for consumers to merge. Only written by builds
+   * with the `native` feature.
+   */
+  idMapPath?: string;
+
+  /**
+   * Add the initialism of 2-5 word descriptors as an alias
+   * (e.g. "Frequently Asked Questions" → `faq`)
+   * @default false
+   */
+  generateAcronyms?: boolean;
+
+  /**
+   * Split camelCase, snake_case and kebab-case identifiers (e.g. a `name` of `submitOrder`)
+   * into words in IDs and aliases. The words also become aliases of their own.
+   * @default false
+   */
+  splitIdentifiers?: boolean;
+
+  /**
+   * Leave the ID's descriptor (e.g. "sign in") out of `data-ui-aliases`, keeping its
+   * synonyms, so the aliases only carry additional search terms
+   * @default false
+   */
+  aliasExcludesIdDescriptor?: boolean;
+
+  /**
+   * Add keywords from the text of the elements an `aria-describedby` references (in the
+   * same file) as aliases, e.g. "purchase" from "Completes your purchase"
+   * @default false
+   */
+  describedbyAliases?: boolean;
+
+  /**
+   * Skip elements that already have data-ui-id. When off, the existing ID is
+   * replaced, and a static one is reused as the descriptor
    * @default true
    */
   skipExisting?: boolean;
 
+  /**
+   * With `skipExisting`, also skip elements whose existing ID is a dynamic expression
+   * (`data-ui-id={someId}`). When false, such IDs are replaced with a generated one.
+   * @default true
+   */
+  skipExistingDynamic?: boolean;
+
+  /**
+   * Use an element's static `id` as its ID exactly, instead of composing an ID from it
+   * @default false
+   */
+  useExistingIdVerbatim?: boolean;
+
+  /**
+   * With `useExistingIdVerbatim`, still put the ID prefix in front (`ui-email`)
+   * @default false
+   */
+  prefixVerbatimIds?: boolean;
+
   /**
    * Only instrument elements inside components with specific names
    * @default []
@@ -75,16 +534,165 @@ export interface UIBridgeSwcPluginConfig {
   onlyInComponents?: string[];
 
   /**
-   * Skip instrumentation for elements inside these components
+   * Skip instrumentation for elements inside these components.
+   * Entries like `'Foo:button'` skip only that tag inside the component.
    * @default []
    */
   skipInComponents?: string[];
 
+  /**
+   * When set (e.g. `'data-ui-scope'`), only descendants of elements carrying this attribute
+   * are instrumented, limiting instrumentation to app-owned regions
+   */
+  scopeAttribute?: string;
+
+  /**
+   * Skip conditionally rendered elements (`{show && <button />}`, `{ok ? <a /> : null}`)
+   * and their descendants, since they may not always be on the page
+   * @default false
+   */
+  skipConditional?: boolean;
+
+  /**
+   * Skip elements that nothing static describes (dynamic text and attributes only), instead
+   * of giving them a positional ID that churns
+   * @default false
+   */
+  skipFullyDynamic?: boolean;
+
+  /**
+   * Skip elements inside an `<svg>` (icons, charts)
+   * @default false
+   */
+  skipSvg?: boolean;
+
+  /**
+   * Tags still instrumented inside an `<svg>` with `skipSvg`, even when they aren't in
+   * `elements` (e.g. `['a', 'circle']` for interactive charts)
+   * @default []
+   */
+  svgInstrumentElements?: string[];
+
+  /**
+   * Only instrument elements matching one of these selectors (empty = all), e.g.
+   * `button.btn-primary` or `[data-cta]`. Supports `tag`, `.class`, `[attr]` and
+   * `[attr=value]`, matched against static attribute values at build time.
+   * @default []
+   */
+  matchSelectors?: string[];
+
   /**
    * Enable verbose logging during build
    * @default false
    */
   verbose?: boolean;
+
+  /**
+   * Log how long each file took to transform
+   * @default false
+   */
+  profile?: boolean;
+
+  /**
+   * With `profile`, files taking longer than this many milliseconds are reported as slow
+   * @default 50
+   */
+  profileThresholdMs?: number;
+
+  /**
+   * Report IDs without a descriptor or with a numeric collision suffix as errors. The
+   * plugin can't fail the build, so it logs them; the Rust `transform` API fails with them.
+   * @default false
+   */
+  failOnWeak?: boolean;
+
+  /**
+   * Prepend config warnings and diagnostics to each transformed file as a
+   * leading block comment, ahead of any directives and imports
+   * @default false
+   */
+  warningsAsComments?: boolean;
+
+  /**
+   * After transforming, check that instrumenting the output again would change nothing,
+   * and fail otherwise. Only debug builds of the plugin run the check.
+   * @default false
+   */
+  selfCheck?: boolean;
+
+  /**
+   * After transforming, warn with any ID carried by more than one element of the file
+   * (e.g. pre-instrumented elements that were skipped)
+   * @default false
+   */
+  validateUniqueness?: boolean;
+
+  /**
+   * Fallback descriptors used when none can be inferred, keyed by tag name
+   * (`form`) or tag name plus glyph text content (`button:×`)
+   * @default { 'button:×': 'close', 'button:✕': 'close', 'button:✖': 'close' }
+   */
+  defaultDescriptors?: Record<string, string>;
+
+  /**
+   * Descriptor sources tried in order, keyed by tag name (e.g.
+   * `{ input: ['name', 'placeholder'] }`). Only the listed sources are used for
+   * that tag; other tags use the built-in chain (`aria_label`, `id`, `name` for `output`,
+   * `meter` and `progress`; `text` then `value` for `option`; `group_label` for `optgroup`;
+   * `alt` first for `img` and `area`).
+   * An explicit label always wins.
+   * @default {}
+   */
+  descriptorSourcesByTag?: Record<string, Array<'id' | 'test_id' | 'text' | 'aria_label' | 'placeholder' | 'title' | 'option' | 'name' | 'href' | 'value' | 'group_label' | 'alt'>>;
+
+  /**
+   * Descriptor sources tried in order for tags without a `descriptorSourcesByTag` entry.
+   * Unset uses the built-in chain, which leaves out `test_id`.
+   */
+  idSources?: Array<'id' | 'test_id' | 'text' | 'aria_label' | 'placeholder' | 'title' | 'option' | 'name' | 'href' | 'value' | 'group_label' | 'alt'>;
+
+  /**
+   * Sources that become aliases, in order, independently of the ID's sources (e.g. leave
+   * out `placeholder` from `idSources` but keep it here). `text` is the primary alias.
+   * @default ['text', 'aria_label', 'placeholder', 'title', 'name', 'alt']
+   */
+  aliasSources?: Array<'text' | 'aria_label' | 'placeholder' | 'title' | 'name' | 'alt'>;
+
+  /**
+   * Semantic types keyed by `className` token (e.g. `{ "nav-link": "nav-link" }`). The first
+   * token of an element's static `className` found here sets `data-ui-type`, overriding
+   * the tag-based type.
+   * @default {}
+   */
+  classnameTypeMap?: Record<string, string>;
+
+  /**
+   * Attributes injected by frameworks (e.g. styled-components' `as` or `forwardedAs`) that
+   * are left out of ID, type and alias inference. They stay on the element.
+   * @default []
+   */
+  ignoreAttributesForInference?: string[];
+
+  /**
+   * Maximum number of attributes an element may hold after injection. Attributes that would
+   * exceed it aren't injected, in injection order (id, type, ..., aliases last). An attribute
+   * the element already has is never injected again.
+   */
+  maxInjectedAttributes?: number;
+
+  /**
+   * How injected attribute values are written: `'string'` emits `data-ui-id="..."`,
+   * `'expression'` emits `data-ui-id={"..."}`
+   * @default 'string'
+   */
+  attributeValueStyle?: 'string' | 'expression';
+
+  /**
+   * Where injected attributes go: after all existing ones, before them, or right after the
+   * last `className`/`class`/`id`/`style` (so they precede event handlers)
+   * @default 'append'
+   */
+  attributePlacement?: 'append' | 'prepend' | 'after_static';
 }
 
 /**
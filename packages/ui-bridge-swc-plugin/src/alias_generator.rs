@@ -2,94 +2,209 @@
 //!
 //! Generates semantic aliases for UI elements to enable fuzzy matching.
 
-use crate::config::PluginConfig;
+use crate::config::{DescriptorSource, PluginConfig};
+use crate::id_generator::split_identifier;
+use crate::synonyms::{locale_synonyms, SynonymGroup};
+use std::borrow::Cow;
+use std::collections::HashSet;
 
 /// Context for generating aliases
 #[derive(Debug, Default)]
 pub struct AliasContext<'a> {
-    pub tag_name: &'a str,
+    /// Explicit descriptor from the label attribute (e.g. `data-ui-label`)
+    pub label: Option<&'a str>,
     pub text_content: Option<&'a str>,
     pub aria_label: Option<&'a str>,
     pub placeholder: Option<&'a str>,
     pub title: Option<&'a str>,
     pub name: Option<&'a str>,
+    /// Non-empty `alt` text of an image
+    pub alt: Option<&'a str>,
+    /// Title of the nearest ancestor carrying one, used when nothing else describes the element
+    pub ancestor_title: Option<&'a str>,
+    /// Text of the elements referenced by `aria-describedby`, whose keywords become aliases
+    pub description: Option<&'a str>,
 }
 
+/// Words too common to be useful as aliases on their own
+const STOP_WORDS: &[&str] = &[
+    "and", "are", "but", "can", "for", "from", "has", "have", "into", "its", "not", "our", "the",
+    "this", "that", "their", "them", "then", "they", "was", "will", "with", "you", "your",
+];
+
 /// Generate aliases for an element
 pub fn generate_aliases(config: &PluginConfig, ctx: &AliasContext) -> Vec<String> {
     let mut aliases: Vec<String> = vec![];
+    fn split<'a>(config: &PluginConfig, s: &'a str) -> Cow<'a, str> {
+        if config.split_identifiers {
+            split_identifier(s)
+        } else {
+            Cow::Borrowed(s)
+        }
+    }
+    let normalize = |s: &str| normalize_for_alias(&split(config, s), config.alias_max_words);
+    // The words of an identifier ("submitOrder" gives "submit" and "order")
+    let identifier_words = |s: &str, aliases: &mut Vec<String>| {
+        if let Cow::Owned(words) = split(config, s) {
+            for word in description_keywords(&normalize_for_alias(&words, None)) {
+                if !aliases.iter().any(|alias| alias == word) {
+                    aliases.push(word.to_string());
+                }
+            }
+        }
+    };
+    let locale_groups = locale_synonyms(config);
+    let locale_groups = locale_groups.as_deref().map_or(&[][..], Vec::as_slice);
+    let text_content = ctx
+        .text_content
+        .filter(|_| config.alias_sources.contains(&DescriptorSource::Text));
 
-    // Add text content as primary alias
-    if let Some(text) = ctx.text_content {
-        let normalized = normalize_for_alias(text);
+    // Add the explicit label, or else the text content, as primary alias
+    if let Some(text) = ctx.label.or(text_content) {
+        let normalized = normalize(text);
         if !normalized.is_empty() {
             aliases.push(normalized.clone());
             // Add synonyms
-            for syn in get_synonyms(&normalized) {
+            for syn in get_synonyms(&normalized, config.synonym_depth, locale_groups) {
                 if !aliases.contains(&syn) {
                     aliases.push(syn);
                 }
             }
+            // Add the initialism ("frequently asked questions" -> "faq") and its synonyms
+            if config.generate_acronyms {
+                if let Some(acronym) = get_acronym(&normalized) {
+                    let synonyms = get_synonyms(&acronym, config.synonym_depth, locale_groups);
+                    for alias in std::iter::once(acronym).chain(synonyms) {
+                        if !aliases.contains(&alias) {
+                            aliases.push(alias);
+                        }
+                    }
+                }
+            }
+            identifier_words(text, &mut aliases);
         }
     }
 
-    // Add aria-label
-    if let Some(label) = ctx.aria_label {
-        let normalized = normalize_for_alias(label);
+    // Add text content when the label took its place
+    if let (Some(_), Some(text)) = (ctx.label, text_content) {
+        let normalized = normalize(text);
         if !normalized.is_empty() && !aliases.contains(&normalized) {
             aliases.push(normalized);
         }
     }
 
-    // Add placeholder
-    if let Some(ph) = ctx.placeholder {
-        let normalized = normalize_for_alias(ph);
-        if !normalized.is_empty() && !aliases.contains(&normalized) {
-            aliases.push(normalized);
+    // Add the other configured sources, in order
+    for source in &config.alias_sources {
+        let value = match source {
+            DescriptorSource::AriaLabel => ctx.aria_label,
+            DescriptorSource::Placeholder => ctx.placeholder,
+            DescriptorSource::Title => ctx.title,
+            DescriptorSource::Name => ctx.name,
+            DescriptorSource::Alt => ctx.alt,
+            // Text is the primary alias, and the other sources don't feed aliases
+            _ => None,
+        };
+        if let Some(value) = value {
+            let normalized = normalize(value);
+            if !normalized.is_empty() && !aliases.contains(&normalized) {
+                aliases.push(normalized);
+                identifier_words(value, &mut aliases);
+            }
         }
     }
 
-    // Add title
-    if let Some(t) = ctx.title {
-        let normalized = normalize_for_alias(t);
-        if !normalized.is_empty() && !aliases.contains(&normalized) {
-            aliases.push(normalized);
+    // Add keywords from the description, unless an alias already has them
+    if let Some(description) = ctx.description {
+        for keyword in description_keywords(&normalize_for_alias(description, None)) {
+            let known = aliases
+                .iter()
+                .any(|alias| alias.split_whitespace().any(|word| word == keyword));
+            if !known {
+                aliases.push(keyword.to_string());
+            }
         }
     }
 
-    // Add name attribute
-    if let Some(n) = ctx.name {
-        let normalized = normalize_for_alias(n);
-        if !normalized.is_empty() && !aliases.contains(&normalized) {
-            aliases.push(normalized);
+    // Fall back to the nearest ancestor title (e.g. a tooltip wrapper around an icon button)
+    if aliases.is_empty() {
+        if let Some(t) = ctx.ancestor_title {
+            let normalized = normalize(t);
+            if !normalized.is_empty() {
+                aliases.push(normalized);
+            }
         }
     }
 
+    // Sort before truncating so the result doesn't depend on source attribute order
+    if config.sort_aliases {
+        aliases.sort();
+    }
+
     // Limit to max aliases
     aliases.truncate(config.max_aliases);
     aliases
 }
 
-/// Format aliases as a comma-separated string
-pub fn format_aliases(aliases: &[String]) -> String {
-    aliases.join(",")
+/// Format aliases as a comma-separated string of at most `max_bytes` bytes
+///
+/// Aliases that don't fit are dropped from the end, never cut.
+pub fn format_aliases(aliases: &[String], max_bytes: Option<usize>) -> String {
+    let max_bytes = max_bytes.unwrap_or(usize::MAX);
+    let mut formatted = String::new();
+    for alias in aliases {
+        let separator = usize::from(!formatted.is_empty());
+        if formatted.len() + separator + alias.len() > max_bytes {
+            break;
+        }
+        if separator > 0 {
+            formatted.push(',');
+        }
+        formatted.push_str(alias);
+    }
+    formatted
 }
 
-/// Normalize text for use as an alias
-fn normalize_for_alias(s: &str) -> String {
+/// Normalize text for use as an alias, optionally keeping at most `max_words` words
+fn normalize_for_alias(s: &str, max_words: Option<usize>) -> String {
     s.trim()
         .to_lowercase()
         .chars()
         .filter(|c| c.is_alphanumeric() || c.is_whitespace())
         .collect::<String>()
         .split_whitespace()
+        .take(max_words.unwrap_or(usize::MAX))
         .collect::<Vec<_>>()
         .join(" ")
 }
 
+/// The words of a normalized description worth searching by, in order and without
+/// duplicates: "completes your purchase" gives "completes" and "purchase"
+fn description_keywords(description: &str) -> Vec<&str> {
+    let mut keywords: Vec<&str> = vec![];
+    for word in description.split_whitespace() {
+        if word.chars().count() >= 3 && !STOP_WORDS.contains(&word) && !keywords.contains(&word) {
+            keywords.push(word);
+        }
+    }
+    keywords
+}
+
+/// Get the initialism of a multi-word alias, when it is 2-5 letters long
+fn get_acronym(text: &str) -> Option<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if !(2..=5).contains(&words.len()) {
+        return None;
+    }
+    words.iter().map(|w| w.chars().next()).collect()
+}
+
 /// Get common synonyms for text
-fn get_synonyms(text: &str) -> Vec<String> {
-    let mut synonyms = vec![];
+///
+/// Synonyms are expanded transitively: each synonym found at one level is itself
+/// looked up at the next, up to `depth` levels. Each group is expanded at most once.
+/// Locale groups (see `synonym_dir`) are checked before the built-in ones.
+fn get_synonyms(text: &str, depth: usize, locale_groups: &[SynonymGroup]) -> Vec<String> {
+    let mut synonyms: Vec<String> = vec![];
 
     // Define synonym groups
     let synonym_map: &[(&[&str], &[&str])] = &[
@@ -185,7 +300,7 @@ fn get_synonyms(text: &str) -> Vec<String> {
         ),
         // Help variations
         (
-            &["help", "support", "info", "information"],
+            &["help", "support", "info", "information", "faq"],
             &["help", "support", "info", "information", "faq"],
         ),
         // Settings variations
@@ -245,50 +360,203 @@ fn get_synonyms(text: &str) -> Vec<String> {
             &["collapse", "less", "show less"],
             &["collapse", "less", "show less", "hide details"],
         ),
+        // Toggle/State variations
+        (
+            &["toggle", "on", "off", "enable", "disable", "activate"],
+            &[
+                "toggle",
+                "on",
+                "off",
+                "enable",
+                "disable",
+                "activate",
+                "deactivate",
+                "switch",
+            ],
+        ),
     ];
 
-    for (triggers, all_synonyms) in synonym_map {
-        if triggers.iter().any(|t| text.contains(t)) {
-            for syn in *all_synonyms {
-                if *syn != text && !synonyms.contains(&syn.to_string()) {
+    let mut expanded_groups: HashSet<usize> = HashSet::new();
+    let mut frontier: Vec<String> = vec![text.to_string()];
+
+    for _ in 0..depth {
+        let mut next_frontier = vec![];
+
+        for term in &frontier {
+            // Locale groups are numbered first, then the built-in ones
+            let group = locale_groups
+                .iter()
+                .position(|g| g.triggers.iter().any(|t| matches_trigger(term, t)))
+                .or_else(|| {
+                    synonym_map
+                        .iter()
+                        .position(|(triggers, _)| triggers.iter().any(|t| matches_trigger(term, t)))
+                        .map(|i| locale_groups.len() + i)
+                });
+
+            let Some(group) = group else { continue };
+            if !expanded_groups.insert(group) {
+                continue;
+            }
+
+            let group_synonyms: Vec<&str> = match locale_groups.get(group) {
+                Some(g) => g.synonyms.iter().map(String::as_str).collect(),
+                None => synonym_map[group - locale_groups.len()].1.to_vec(),
+            };
+            for syn in group_synonyms {
+                if syn != text && !synonyms.contains(&syn.to_string()) {
                     synonyms.push(syn.to_string());
+                    next_frontier.push(syn.to_string());
                 }
             }
+        }
+
+        if next_frontier.is_empty() {
             break;
         }
+        frontier = next_frontier;
     }
 
     synonyms
 }
 
+/// Triggers that must match a whole word, so that e.g. "on" doesn't match "continue"
+const WHOLE_WORD_TRIGGERS: &[&str] = &["on", "off"];
+
+/// Check if text contains a synonym trigger
+fn matches_trigger(text: &str, trigger: &str) -> bool {
+    if WHOLE_WORD_TRIGGERS.contains(&trigger) {
+        text.split_whitespace().any(|word| word == trigger)
+    } else {
+        text.contains(trigger)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_normalize_for_alias() {
-        assert_eq!(normalize_for_alias("  Sign In  "), "sign in");
-        assert_eq!(normalize_for_alias("Submit!@#Form"), "submitform");
-        assert_eq!(normalize_for_alias("Enter Email"), "enter email");
+        assert_eq!(normalize_for_alias("  Sign In  ", None), "sign in");
+        assert_eq!(normalize_for_alias("Submit!@#Form", None), "submitform");
+        assert_eq!(normalize_for_alias("Enter Email", None), "enter email");
+        assert_eq!(
+            normalize_for_alias("Enter your email", Some(2)),
+            "enter your"
+        );
     }
 
     #[test]
     fn test_get_synonyms() {
-        let synonyms = get_synonyms("sign in");
+        let synonyms = get_synonyms("sign in", 1, &[]);
         assert!(synonyms.contains(&"signin".to_string()));
         assert!(synonyms.contains(&"login".to_string()));
         assert!(synonyms.contains(&"log in".to_string()));
 
-        let synonyms = get_synonyms("submit");
+        let synonyms = get_synonyms("submit", 1, &[]);
         assert!(synonyms.contains(&"send".to_string()));
         assert!(synonyms.contains(&"confirm".to_string()));
     }
 
+    #[test]
+    fn test_get_synonyms_toggle() {
+        let synonyms = get_synonyms("enable", 1, &[]);
+        assert!(synonyms.contains(&"on".to_string()));
+        assert!(synonyms.contains(&"activate".to_string()));
+        assert!(!synonyms.contains(&"enable".to_string()));
+
+        let synonyms = get_synonyms("turn off", 1, &[]);
+        assert!(synonyms.contains(&"disable".to_string()));
+
+        // "on" only matches as a whole word
+        assert!(get_synonyms("bonus", 1, &[]).is_empty());
+        assert!(!get_synonyms("continue", 1, &[]).contains(&"toggle".to_string()));
+    }
+
+    #[test]
+    fn test_matches_trigger() {
+        assert!(matches_trigger("turn on", "on"));
+        assert!(!matches_trigger("button", "on"));
+        assert!(!matches_trigger("office", "off"));
+        // Other triggers still match anywhere
+        assert!(matches_trigger("submitting", "submit"));
+        assert!(matches_trigger("address", "add"));
+    }
+
+    #[test]
+    fn test_generate_aliases_locale_synonyms() {
+        use crate::synonyms::{insert_synonym_sets, SynonymSets};
+
+        let group = |triggers: &[&str], synonyms: &[&str]| SynonymGroup {
+            triggers: triggers.iter().map(|s| s.to_string()).collect(),
+            synonyms: synonyms.iter().map(|s| s.to_string()).collect(),
+        };
+        insert_synonym_sets(
+            "/synonyms/aliases",
+            SynonymSets::from([
+                (
+                    "de".to_string(),
+                    vec![group(&["speichern"], &["speichern", "sichern", "save"])],
+                ),
+                (
+                    "fr".to_string(),
+                    vec![group(&["enregistrer"], &["enregistrer", "sauvegarder"])],
+                ),
+            ]),
+        );
+
+        let config = |locale: &str| PluginConfig {
+            synonym_dir: Some("/synonyms/aliases".into()),
+            locale: Some(locale.into()),
+            ..Default::default()
+        };
+        let ctx = |text| AliasContext {
+            text_content: Some(text),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            generate_aliases(&config("de"), &ctx("Speichern")),
+            ["speichern", "sichern", "save"]
+        );
+        assert_eq!(
+            generate_aliases(&config("fr"), &ctx("Speichern")),
+            ["speichern"]
+        );
+        assert_eq!(
+            generate_aliases(&config("fr"), &ctx("Enregistrer")),
+            ["enregistrer", "sauvegarder"]
+        );
+
+        // Built-in groups still apply, after the locale ones
+        let aliases = generate_aliases(&config("de"), &ctx("Submit"));
+        assert!(aliases.contains(&"send".to_string()));
+    }
+
+    #[test]
+    fn test_get_synonyms_transitive() {
+        // "submit" -> "confirm" -> "agree" only with a second hop
+        let synonyms = get_synonyms("submit", 1, &[]);
+        assert!(!synonyms.contains(&"agree".to_string()));
+
+        let synonyms = get_synonyms("submit", 2, &[]);
+        assert!(synonyms.contains(&"confirm".to_string()));
+        assert!(synonyms.contains(&"agree".to_string()));
+
+        // Groups are expanded once, so deep expansion terminates without duplicates
+        let synonyms = get_synonyms("submit", 10, &[]);
+        let unique: HashSet<&String> = synonyms.iter().collect();
+        assert_eq!(unique.len(), synonyms.len());
+        assert!(!synonyms.contains(&"submit".to_string()));
+
+        assert!(get_synonyms("submit", 0, &[]).is_empty());
+    }
+
     #[test]
     fn test_generate_aliases() {
         let config = PluginConfig::default();
         let ctx = AliasContext {
-            tag_name: "button",
             text_content: Some("Sign In"),
             ..Default::default()
         };
@@ -298,11 +566,88 @@ mod tests {
         assert!(aliases.contains(&"login".to_string()) || aliases.contains(&"signin".to_string()));
     }
 
+    #[test]
+    fn test_generate_aliases_split_identifiers() {
+        let config = PluginConfig {
+            split_identifiers: true,
+            ..Default::default()
+        };
+        for name in ["submitOrder", "submit_order", "submit-order"] {
+            let ctx = AliasContext {
+                name: Some(name),
+                ..Default::default()
+            };
+            assert_eq!(
+                generate_aliases(&config, &ctx),
+                vec!["submit order", "submit", "order"],
+                "{}",
+                name
+            );
+        }
+
+        // Text with spaces isn't an identifier
+        let ctx = AliasContext {
+            placeholder: Some("Search orders"),
+            ..Default::default()
+        };
+        assert_eq!(generate_aliases(&config, &ctx), vec!["search orders"]);
+
+        // Off by default, identifiers stay one token
+        let ctx = AliasContext {
+            name: Some("submit_order"),
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_aliases(&PluginConfig::default(), &ctx),
+            vec!["submitorder"]
+        );
+    }
+
+    #[test]
+    fn test_get_acronym() {
+        assert_eq!(
+            get_acronym("frequently asked questions"),
+            Some("faq".into())
+        );
+        assert_eq!(
+            get_acronym("application programming interface"),
+            Some("api".into())
+        );
+        assert_eq!(get_acronym("save"), None);
+        assert_eq!(get_acronym("a b c d e f"), None);
+    }
+
+    #[test]
+    fn test_generate_aliases_acronyms() {
+        let ctx = AliasContext {
+            text_content: Some("Frequently Asked Questions"),
+            ..Default::default()
+        };
+
+        let aliases = generate_aliases(&PluginConfig::default(), &ctx);
+        assert!(!aliases.contains(&"faq".to_string()));
+
+        let config = PluginConfig {
+            generate_acronyms: true,
+            ..Default::default()
+        };
+        let aliases = generate_aliases(&config, &ctx);
+        assert_eq!(aliases[..2], ["frequently asked questions", "faq"]);
+        assert!(aliases.contains(&"help".to_string()));
+
+        // And the reverse, through the help group
+        let ctx = AliasContext {
+            text_content: Some("FAQ"),
+            ..Default::default()
+        };
+        let aliases = generate_aliases(&PluginConfig::default(), &ctx);
+        assert!(aliases.contains(&"help".to_string()));
+    }
+
     #[test]
     fn test_generate_aliases_with_multiple_sources() {
         let config = PluginConfig::default();
         let ctx = AliasContext {
-            tag_name: "input",
             placeholder: Some("Email address"),
             aria_label: Some("Enter your email"),
             ..Default::default()
@@ -313,13 +658,96 @@ mod tests {
         assert!(aliases.contains(&"enter your email".to_string()));
     }
 
+    #[test]
+    fn test_generate_aliases_ancestor_title() {
+        let config = PluginConfig::default();
+        let ctx = AliasContext {
+            text_content: Some("🗑"),
+            ancestor_title: Some("Delete"),
+            ..Default::default()
+        };
+        assert_eq!(generate_aliases(&config, &ctx), vec!["delete".to_string()]);
+
+        // Only used when the element has nothing of its own
+        let ctx = AliasContext {
+            aria_label: Some("Remove row"),
+            ancestor_title: Some("Delete"),
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_aliases(&config, &ctx),
+            vec!["remove row".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_generate_aliases_description() {
+        let config = PluginConfig {
+            max_aliases: 10,
+            ..Default::default()
+        };
+        let ctx = AliasContext {
+            text_content: Some("Buy"),
+            description: Some("Completes your purchase. Buy now!"),
+            ..Default::default()
+        };
+        let aliases = generate_aliases(&config, &ctx);
+        assert_eq!(aliases[0], "buy");
+        assert!(aliases.ends_with(&[
+            "completes".to_string(),
+            "purchase".to_string(),
+            "now".to_string()
+        ]));
+        // Stop words and words already aliased are left out
+        assert!(!aliases.contains(&"your".to_string()));
+        assert_eq!(aliases.iter().filter(|alias| *alias == "buy").count(), 1);
+
+        assert_eq!(
+            description_keywords("sign in to your account and the account settings"),
+            vec!["sign", "account", "settings"]
+        );
+    }
+
+    #[test]
+    fn test_generate_aliases_sources() {
+        let ctx = AliasContext {
+            text_content: Some("Search"),
+            aria_label: Some("Site search"),
+            placeholder: Some("Type to search"),
+            name: Some("q"),
+            ..Default::default()
+        };
+
+        let config = PluginConfig {
+            alias_sources: vec![DescriptorSource::Placeholder, DescriptorSource::Name],
+            ..Default::default()
+        };
+        assert_eq!(generate_aliases(&config, &ctx), vec!["type to search", "q"]);
+
+        // Order follows the list, after the text
+        let config = PluginConfig {
+            alias_sources: vec![
+                DescriptorSource::Name,
+                DescriptorSource::AriaLabel,
+                DescriptorSource::Text,
+            ],
+            max_aliases: 10,
+            ..Default::default()
+        };
+        let aliases = generate_aliases(&config, &ctx);
+        assert_eq!(aliases[0], "search");
+        assert!(aliases.ends_with(&["q".to_string(), "site search".to_string()]));
+        assert!(!aliases.contains(&"type to search".to_string()));
+    }
+
     #[test]
     fn test_max_aliases() {
-        let mut config = PluginConfig::default();
-        config.max_aliases = 2;
+        let config = PluginConfig {
+            max_aliases: 2,
+            ..Default::default()
+        };
 
         let ctx = AliasContext {
-            tag_name: "button",
             text_content: Some("Sign In"),
             aria_label: Some("Login button"),
             placeholder: Some("Click to sign in"),
@@ -330,9 +758,67 @@ mod tests {
         assert!(aliases.len() <= 2);
     }
 
+    #[test]
+    fn test_sort_aliases() {
+        let config = PluginConfig {
+            sort_aliases: true,
+            ..Default::default()
+        };
+        let ctx = AliasContext {
+            placeholder: Some("Email address"),
+            aria_label: Some("Enter your email"),
+            name: Some("contact"),
+            ..Default::default()
+        };
+
+        let aliases = generate_aliases(&config, &ctx);
+        assert_eq!(
+            aliases,
+            vec!["contact", "email address", "enter your email"]
+        );
+
+        // Swapping which attribute carries which text yields the same output
+        let ctx = AliasContext {
+            placeholder: Some("Enter your email"),
+            aria_label: Some("contact"),
+            name: Some("Email address"),
+            ..Default::default()
+        };
+        assert_eq!(generate_aliases(&config, &ctx), aliases);
+    }
+
     #[test]
     fn test_format_aliases() {
         let aliases = vec!["sign in".to_string(), "login".to_string()];
-        assert_eq!(format_aliases(&aliases), "sign in,login");
+        assert_eq!(format_aliases(&aliases, None), "sign in,login");
+    }
+
+    #[test]
+    fn test_format_aliases_max_bytes() {
+        let aliases = vec![
+            "reset your account password".to_string(),
+            "reset password".to_string(),
+            "password".to_string(),
+        ];
+
+        assert_eq!(
+            format_aliases(&aliases, Some(100)),
+            "reset your account password,reset password,password"
+        );
+        // Exactly fits the first two and their separator
+        assert_eq!(
+            format_aliases(&aliases, Some(42)),
+            "reset your account password,reset password"
+        );
+        assert_eq!(
+            format_aliases(&aliases, Some(41)),
+            "reset your account password"
+        );
+        assert_eq!(format_aliases(&aliases, Some(10)), "");
+
+        // Budgets count bytes, not characters
+        let aliases = vec!["café".to_string(), "menu".to_string()];
+        assert_eq!(format_aliases(&aliases, Some(9)), "café");
+        assert_eq!(format_aliases(&aliases, Some(10)), "café,menu");
     }
 }
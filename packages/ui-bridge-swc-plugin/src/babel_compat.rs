@@ -0,0 +1,479 @@
+//! Babel plugin compatibility
+//!
+//! Reproduces the IDs, types and aliases of `@qontinui/ui-bridge-babel-plugin`, so selectors
+//! saved against it keep matching after a migration (see `legacy_babel_compat`). The
+//! functions mirror `id-generator.ts` and `alias-generator.ts` rather than the rest of this
+//! crate, quirks included.
+
+use crate::config::PluginConfig;
+use crate::id_generator::{GeneratedId, IdConfidence};
+use swc_core::ecma::ast::JSXElementChild;
+
+/// Element information as the Babel plugin sees it
+#[derive(Debug, Clone, Default)]
+pub struct BabelContext<'a> {
+    pub component_name: Option<&'a str>,
+    pub file_path: &'a str,
+    pub tag_name: &'a str,
+    /// Direct text children (see `extract_text_content`)
+    pub text_content: Option<&'a str>,
+    pub aria_label: Option<&'a str>,
+    pub placeholder: Option<&'a str>,
+    pub title: Option<&'a str>,
+    pub name: Option<&'a str>,
+    /// The `id` attribute
+    pub existing_id: Option<&'a str>,
+    /// Zero-based index of the element among the file's elements with the same tag
+    pub element_index: usize,
+}
+
+/// The Babel plugin's synonym table
+const SYNONYMS: &[(&str, &[&str])] = &[
+    ("submit", &["send", "go", "confirm", "done", "ok", "apply"]),
+    ("cancel", &["close", "dismiss", "abort", "back", "exit"]),
+    ("delete", &["remove", "trash", "erase", "clear"]),
+    ("edit", &["modify", "change", "update"]),
+    ("add", &["create", "new", "plus", "insert"]),
+    ("save", &["store", "keep", "preserve"]),
+    ("search", &["find", "lookup", "query"]),
+    ("login", &["signin", "sign in", "log in"]),
+    ("logout", &["signout", "sign out", "log out"]),
+    ("register", &["signup", "sign up", "join"]),
+    ("next", &["continue", "forward", "proceed"]),
+    ("previous", &["back", "prev", "prior"]),
+    ("start", &["begin", "launch", "run"]),
+    ("stop", &["end", "halt", "pause"]),
+    ("upload", &["attach", "import"]),
+    ("download", &["export", "get"]),
+    ("settings", &["preferences", "options", "config"]),
+    ("help", &["support", "info", "about"]),
+    ("home", &["main", "dashboard"]),
+    ("profile", &["account", "user"]),
+];
+
+/// Extract the text of the direct text children, each trimmed and joined with a space.
+/// Text in nested elements and expressions doesn't count.
+pub fn extract_text_content(children: &[JSXElementChild]) -> Option<String> {
+    let parts: Vec<&str> = children
+        .iter()
+        .filter_map(|child| match child {
+            JSXElementChild::JSXText(text) => Some(text.value.trim()),
+            _ => None,
+        })
+        .filter(|text| !text.is_empty())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// Babel's `simpleHash`: a 32-bit string hash over UTF-16 code units, as hex
+fn simple_hash(s: &str) -> String {
+    let hash = s.encode_utf16().fold(0i32, |hash, unit| {
+        hash.wrapping_shl(5)
+            .wrapping_sub(hash)
+            .wrapping_add(i32::from(unit))
+    });
+    let mut hex = format!("{:x}", i64::from(hash).abs());
+    hex.truncate(8);
+    hex
+}
+
+/// Lowercase, dash-separate and cut to 30 characters. The cut comes last, so it can
+/// leave a trailing dash.
+fn normalize_for_id(text: &str) -> String {
+    let mut normalized = String::new();
+    for c in text.to_lowercase().trim().chars() {
+        if c.is_ascii_lowercase() || c.is_ascii_digit() {
+            normalized.push(c);
+        } else if !normalized.ends_with('-') {
+            normalized.push('-');
+        }
+    }
+    normalized.trim_matches('-').chars().take(30).collect()
+}
+
+/// The file component of an ID: the file name without its extension, or the folder
+/// name for index files
+fn component_from_path(file_path: &str) -> Option<String> {
+    let normalized = file_path.replace('\\', "/");
+    let (rest, file_name) = normalized.rsplit_once('/')?;
+    let (stem, extension) = file_name.rsplit_once('.')?;
+    if stem.is_empty() || !matches!(extension, "ts" | "tsx" | "js" | "jsx") {
+        return None;
+    }
+    if stem == "index" {
+        if let Some(folder) = rest.rsplit('/').next().filter(|f| !f.is_empty()) {
+            return Some(normalize_for_id(folder));
+        }
+    }
+    Some(normalize_for_id(stem))
+}
+
+/// Get the Babel plugin's semantic type for an element
+pub fn get_semantic_type(tag_name: &str, text_content: Option<&str>) -> String {
+    let tag = tag_name.to_lowercase();
+    let semantic_type = match tag.as_str() {
+        "input" => "input",
+        "button" => {
+            let text = text_content.unwrap_or("").to_lowercase();
+            if text.contains("submit") || text.contains("save") {
+                "submit-button"
+            } else if text.contains("cancel") || text.contains("close") {
+                "cancel-button"
+            } else if text.contains("delete") || text.contains("remove") {
+                "delete-button"
+            } else {
+                "button"
+            }
+        }
+        "a" => "link",
+        "select" => "dropdown",
+        "nav" => "navigation",
+        "aside" => "sidebar",
+        "img" => "image",
+        _ => return tag,
+    };
+    semantic_type.to_string()
+}
+
+/// Generate an ID the way the Babel plugin does
+pub fn generate_id(config: &PluginConfig, ctx: &BabelContext) -> GeneratedId {
+    let prefix = config.id_prefix_for(ctx.component_name);
+    let mut parts = vec![prefix.to_string()];
+
+    if config.include_file_path {
+        parts.extend(component_from_path(ctx.file_path));
+    }
+
+    if config.include_component_name {
+        parts.extend(ctx.component_name.map(normalize_for_id));
+    }
+
+    // Empty attributes are skipped, like falsy strings in JS
+    let descriptor = [
+        ctx.text_content,
+        ctx.aria_label,
+        ctx.placeholder,
+        ctx.title,
+        ctx.existing_id,
+    ]
+    .into_iter()
+    .flatten()
+    .find(|text| !text.is_empty())
+    .map(normalize_for_id)
+    .filter(|descriptor| !descriptor.is_empty());
+    parts.extend(descriptor.clone());
+
+    parts.push(get_semantic_type(ctx.tag_name, ctx.text_content));
+
+    parts.retain(|part| !part.is_empty());
+    let mut id = parts.join("-");
+    let mut confidence = if descriptor.is_some() {
+        IdConfidence::Medium
+    } else {
+        IdConfidence::Low
+    };
+
+    if id.encode_utf16().count() < 5 {
+        id = format!("{}-{}-{}", prefix, ctx.tag_name, ctx.element_index);
+    }
+
+    if config.hash_ids {
+        let hash = simple_hash(&format!("{}{}{}", id, ctx.file_path, ctx.element_index));
+        id = format!("{}-{}", prefix, hash);
+        confidence = IdConfidence::Low;
+    }
+
+    GeneratedId {
+        id,
+        confidence,
+        descriptor,
+    }
+}
+
+/// Lowercase, drop punctuation and collapse whitespace. Surrounding whitespace is
+/// trimmed before punctuation is dropped, so `"! Go"` keeps a leading space.
+fn normalize_text(text: &str) -> String {
+    let mut normalized = String::new();
+    for c in text.to_lowercase().trim().chars() {
+        if c.is_whitespace() {
+            if !normalized.ends_with(' ') {
+                normalized.push(' ');
+            }
+        } else if c.is_ascii_lowercase() || c.is_ascii_digit() {
+            normalized.push(c);
+        }
+    }
+    normalized
+}
+
+/// Synonyms of a word, or the other members of the group it's a synonym in
+fn synonyms(word: &str) -> Vec<&'static str> {
+    if let Some((_, values)) = SYNONYMS.iter().find(|(key, _)| *key == word) {
+        return values.to_vec();
+    }
+    SYNONYMS
+        .iter()
+        .find(|(_, values)| values.contains(&word))
+        .map(|(key, values)| {
+            std::iter::once(*key)
+                .chain(values.iter().copied().filter(|v| *v != word))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The alias naming an element's tag
+fn type_alias(tag_name: &str) -> Option<&'static str> {
+    match tag_name.to_lowercase().as_str() {
+        "button" => Some("button"),
+        "input" => Some("input"),
+        "select" => Some("dropdown"),
+        "textarea" => Some("text area"),
+        "a" => Some("link"),
+        "form" => Some("form"),
+        "nav" => Some("navigation"),
+        "img" => Some("image"),
+        _ => None,
+    }
+}
+
+/// Generate aliases the way the Babel plugin does. Elements without text, aria-label,
+/// placeholder, title or name get none.
+pub fn generate_aliases(config: &PluginConfig, ctx: &BabelContext) -> Vec<String> {
+    fn present(value: Option<&str>) -> Option<&str> {
+        value.filter(|v| !v.is_empty())
+    }
+    let has_content = [
+        ctx.text_content,
+        ctx.aria_label,
+        ctx.placeholder,
+        ctx.title,
+        ctx.name,
+    ]
+    .into_iter()
+    .any(|value| present(value).is_some());
+    if !has_content {
+        return vec![];
+    }
+
+    let mut aliases: Vec<String> = vec![];
+    let mut add = |alias: String| {
+        if !alias.is_empty() && !aliases.contains(&alias) {
+            aliases.push(alias);
+        }
+    };
+
+    if let Some(text) = present(ctx.text_content) {
+        let normalized = normalize_text(text);
+        if !normalized.is_empty() {
+            let tokens: Vec<&str> = normalized
+                .split(' ')
+                .filter(|token| token.len() > 1)
+                .collect();
+            add(normalized.clone());
+            for token in &tokens {
+                if token.len() >= 3 {
+                    add(token.to_string());
+                }
+                for synonym in synonyms(token) {
+                    add(synonym.to_string());
+                }
+            }
+            for pair in tokens.windows(2) {
+                add(pair.join(" "));
+            }
+        }
+    }
+
+    for value in [ctx.aria_label, ctx.placeholder, ctx.title]
+        .into_iter()
+        .filter_map(present)
+    {
+        add(normalize_text(value));
+    }
+
+    if let Some(name) = present(ctx.name) {
+        // Dashes are already gone by the time they'd become spaces
+        add(normalize_text(name).replace('-', " "));
+    }
+
+    add(type_alias(ctx.tag_name).unwrap_or_default().to_string());
+
+    aliases
+        .into_iter()
+        .filter(|alias| (2..=50).contains(&alias.len()))
+        .take(config.max_aliases)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn button(text: &str) -> BabelContext<'_> {
+        BabelContext {
+            file_path: "/app/src/components/LoginForm.tsx",
+            tag_name: "button",
+            text_content: Some(text),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_simple_hash() {
+        assert_eq!(simple_hash(""), "0");
+        assert_eq!(simple_hash("a"), "61");
+        assert_eq!(simple_hash("abc"), "17862");
+        // Overflows 32 bits
+        assert_eq!(simple_hash("hello world"), "6aefe2c4");
+    }
+
+    #[test]
+    fn test_normalize_for_id() {
+        assert_eq!(normalize_for_id("  Sign In! "), "sign-in");
+        assert_eq!(normalize_for_id("LoginForm"), "loginform");
+        // Cut after the dashes are placed
+        assert_eq!(
+            normalize_for_id("Create a new account for your team"),
+            "create-a-new-account-for-your-"
+        );
+    }
+
+    #[test]
+    fn test_component_from_path() {
+        assert_eq!(
+            component_from_path("/app/src/LoginForm.tsx").as_deref(),
+            Some("loginform")
+        );
+        assert_eq!(
+            component_from_path("C:\\app\\Settings\\index.jsx").as_deref(),
+            Some("settings")
+        );
+        assert_eq!(component_from_path("/app/styles.css"), None);
+    }
+
+    #[test]
+    fn test_generate_id() {
+        let config = PluginConfig::default();
+        let id = |ctx: BabelContext| generate_id(&config, &ctx).id;
+
+        assert_eq!(id(button("Submit Form")), "ui-submit-form-submit-button");
+        assert_eq!(
+            id(BabelContext {
+                component_name: Some("LoginForm"),
+                ..button("Sign In")
+            }),
+            "ui-loginform-sign-in-button"
+        );
+        assert_eq!(
+            id(BabelContext {
+                tag_name: "input",
+                text_content: None,
+                placeholder: Some("Enter email"),
+                ..button("")
+            }),
+            "ui-enter-email-input"
+        );
+        assert_eq!(
+            id(BabelContext {
+                text_content: None,
+                aria_label: Some("Close dialog"),
+                ..button("")
+            }),
+            "ui-close-dialog-button"
+        );
+        assert_eq!(
+            id(BabelContext {
+                tag_name: "a",
+                element_index: 2,
+                ..button("")
+            }),
+            "ui-link"
+        );
+
+        // Too short, so positional
+        let config = PluginConfig {
+            id_prefix: String::new(),
+            ..Default::default()
+        };
+        let ctx = BabelContext {
+            tag_name: "a",
+            element_index: 2,
+            ..button("")
+        };
+        assert_eq!(generate_id(&config, &ctx).id, "-a-2");
+    }
+
+    #[test]
+    fn test_generate_id_with_file_path_and_hash() {
+        let config = PluginConfig {
+            include_file_path: true,
+            ..Default::default()
+        };
+        let ctx = BabelContext {
+            component_name: Some("LoginForm"),
+            ..button("Cancel")
+        };
+        assert_eq!(
+            generate_id(&config, &ctx).id,
+            "ui-loginform-loginform-cancel-cancel-button"
+        );
+
+        let config = PluginConfig {
+            hash_ids: true,
+            ..Default::default()
+        };
+        let expected = simple_hash("ui-cancel-cancel-button/app/src/components/LoginForm.tsx0");
+        assert_eq!(
+            generate_id(&config, &button("Cancel")).id,
+            format!("ui-{}", expected)
+        );
+    }
+
+    #[test]
+    fn test_get_semantic_type() {
+        assert_eq!(get_semantic_type("button", Some("Save")), "submit-button");
+        assert_eq!(get_semantic_type("button", Some("Close")), "cancel-button");
+        assert_eq!(get_semantic_type("button", Some("Remove")), "delete-button");
+        assert_eq!(get_semantic_type("button", None), "button");
+        assert_eq!(get_semantic_type("select", None), "dropdown");
+        assert_eq!(get_semantic_type("aside", None), "sidebar");
+        assert_eq!(get_semantic_type("textarea", None), "textarea");
+        assert_eq!(get_semantic_type("section", None), "section");
+    }
+
+    #[test]
+    fn test_generate_aliases() {
+        let config = PluginConfig::default();
+        assert_eq!(
+            generate_aliases(&config, &button("Submit")),
+            ["submit", "send", "go", "confirm", "done"]
+        );
+        assert_eq!(
+            generate_aliases(&config, &button("Sign In")),
+            ["sign in", "sign", "button"]
+        );
+
+        let config = PluginConfig {
+            max_aliases: 10,
+            ..Default::default()
+        };
+        let ctx = BabelContext {
+            tag_name: "input",
+            text_content: None,
+            placeholder: Some("Email address"),
+            name: Some("user-email"),
+            ..button("")
+        };
+        assert_eq!(
+            generate_aliases(&config, &ctx),
+            ["email address", "useremail", "input"]
+        );
+
+        let empty = BabelContext {
+            text_content: None,
+            ..button("")
+        };
+        assert!(generate_aliases(&config, &empty).is_empty());
+    }
+}
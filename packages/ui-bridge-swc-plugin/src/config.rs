@@ -2,7 +2,10 @@
 //!
 //! Handles parsing and default values for plugin configuration options.
 
+use crate::selectors::Selector;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 
 /// Plugin configuration options
 ///
@@ -10,14 +13,47 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PluginConfig {
+    /// Whether to instrument at all; when off, files are returned unchanged
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
     /// Elements to instrument (e.g., ["button", "input", "a"])
     #[serde(default = "default_elements")]
     pub elements: Vec<String>,
 
+    /// Extra elements to instrument on top of `elements` (e.g., ["dialog"])
+    #[serde(default)]
+    pub additional_elements: Vec<String>,
+
+    /// Which lowercase tags count as HTML elements rather than components
+    #[serde(default)]
+    pub html_element_predicate: HtmlElementPredicate,
+
+    /// Tags counted as HTML elements with the `explicit_list` predicate
+    #[serde(default)]
+    pub html_elements: Vec<String>,
+
+    /// Also instrument any element with a truthy `contenteditable`, even if not in `elements`
+    #[serde(default)]
+    pub detect_contenteditable: bool,
+
     /// Prefix for generated IDs
     #[serde(default = "default_id_prefix")]
     pub id_prefix: String,
 
+    /// ID prefixes by component name (e.g. `{ "CheckoutForm": "checkout" }`), overriding
+    /// `id_prefix` for elements rendered in that component
+    #[serde(default)]
+    pub component_prefixes: HashMap<String, String>,
+
+    /// Separator between the parts of an ID (prefix, component, descriptor, type)
+    #[serde(default = "default_separator")]
+    pub id_separator: String,
+
+    /// Separator between the words within a part (`login_form` with `"_"`)
+    #[serde(default = "default_separator")]
+    pub word_separator: String,
+
     /// Attribute name for the generated ID
     #[serde(default = "default_id_attribute")]
     pub id_attribute: String,
@@ -30,6 +66,111 @@ pub struct PluginConfig {
     #[serde(default = "default_type_attribute")]
     pub type_attribute: String,
 
+    /// Attribute name for the element's ARIA role
+    #[serde(default = "default_role_attribute")]
+    pub role_attribute: String,
+
+    /// Whether to emit the explicit or implicit ARIA role
+    #[serde(default)]
+    pub emit_role_attribute: bool,
+
+    /// Attribute name for the element's extracted text
+    #[serde(default = "default_text_attribute")]
+    pub text_attribute: String,
+
+    /// Whether to emit the text the ID was derived from (after `text_join_strategy`,
+    /// captions and wrapping labels, before normalization), to trace an ID back to its source
+    #[serde(default)]
+    pub emit_text_attribute: bool,
+
+    /// Attribute name for the `.map()` index hint
+    #[serde(default = "default_index_key_attribute")]
+    pub index_key_attribute: String,
+
+    /// Expose the `.map()` callback index on list items that have no static descriptor
+    #[serde(default)]
+    pub emit_index_key: bool,
+
+    /// Attribute name for the ID confidence tier
+    #[serde(default = "default_confidence_attribute")]
+    pub confidence_attribute: String,
+
+    /// Emit how reliable each ID is (`high`, `medium` or `low`)
+    #[serde(default)]
+    pub emit_confidence: bool,
+
+    /// Attribute name for the primary action marker
+    #[serde(default = "default_primary_attribute")]
+    pub primary_attribute: String,
+
+    /// Mark each form's primary action (its default submit button) with `primary_attribute`
+    #[serde(default)]
+    pub mark_primary_actions: bool,
+
+    /// Attribute name for the portal marker
+    #[serde(default = "default_portal_attribute")]
+    pub portal_attribute: String,
+
+    /// Mark elements rendered through `createPortal` with `portal_attribute`, since they
+    /// sit outside their component's DOM subtree
+    #[serde(default)]
+    pub portal_component_hint: bool,
+
+    /// Attribute name for a radio button's group (its shared `name`)
+    #[serde(default = "default_group_attribute")]
+    pub group_attribute: String,
+
+    /// Attribute holding an explicit descriptor, used before any inferred one and stripped
+    #[serde(default = "default_label_attribute")]
+    pub label_attribute: String,
+
+    /// Attribute that opts an element out of instrumentation
+    ///
+    /// Its value is a comma-separated set of `id`, `type`, `aliases` or `all` (the default).
+    /// It's kept in the output, so instrumenting again leaves the element alone.
+    #[serde(default = "default_ignore_attribute")]
+    pub ignore_attribute: String,
+
+    /// Whether to emit separate id/type/aliases attributes or a single combined one
+    #[serde(default)]
+    pub emit_mode: EmitMode,
+
+    /// Attribute name for the combined value in compact mode
+    #[serde(default = "default_compact_attribute")]
+    pub compact_attribute: String,
+
+    /// Delimiter between id, type and aliases in compact mode
+    #[serde(default = "default_compact_delimiter")]
+    pub compact_delimiter: String,
+
+    /// The build being instrumented. `development` emits every attribute, `production` only
+    /// `production_attributes`; unset emits what the other options ask for.
+    #[serde(default)]
+    pub environment: Option<Environment>,
+
+    /// The attributes emitted, at most, in a `production` build
+    #[serde(default = "default_production_attributes")]
+    pub production_attributes: Vec<InjectedAttribute>,
+
+    /// Also set the element's `id` to the generated ID, so `document.getElementById` finds
+    /// it. An existing `id` is never replaced. In `.map()` callbacks the index is appended,
+    /// and items of callbacks without an index parameter get none.
+    #[serde(default)]
+    pub mirror_to_id: bool,
+
+    /// How separate text nodes of an element (`Save<br/>Draft`) combine into its descriptor
+    #[serde(default)]
+    pub text_join_strategy: TextJoinStrategy,
+
+    /// Use a descendant's `aria-label` (or `title`) when an element has no text of its own
+    #[serde(default)]
+    pub derive_from_child_aria: bool,
+
+    /// Use the text of a static `dangerouslySetInnerHTML={{ __html: "..." }}`, with its tags
+    /// stripped, when an element has no text children
+    #[serde(default)]
+    pub extract_inner_html: bool,
+
     /// Whether to generate aliases from text content
     #[serde(default = "default_true")]
     pub generate_aliases: bool,
@@ -38,6 +179,16 @@ pub struct PluginConfig {
     #[serde(default = "default_true")]
     pub include_component_name: bool,
 
+    /// Whether JSX passed as children or props to another component (`<Card><button/></Card>`)
+    /// is named after the component it's written in, rather than the one it's passed to
+    #[serde(default = "default_true")]
+    pub children_inherit_parent_component: bool,
+
+    /// Maximum number of nested components tracked. Components nested deeper are treated
+    /// as part of the enclosing one, bounding the work done per component.
+    #[serde(default)]
+    pub max_component_depth: Option<usize>,
+
     /// Whether to include file path in generated ID
     #[serde(default)]
     pub include_file_path: bool,
@@ -46,14 +197,157 @@ pub struct PluginConfig {
     #[serde(default)]
     pub hash_ids: bool,
 
+    /// Salt collision suffixes with a hash of the project-relative file path, so they don't
+    /// repeat across files (with `hash_ids`, the path is folded into every hash)
+    #[serde(default)]
+    pub guarantee_global_unique: bool,
+
+    /// IDs the plugin must never emit, e.g. ones the app or runtime assigns itself. An
+    /// element that would get one is suffixed with its index, like a collision.
+    #[serde(default)]
+    pub reserved_ids: Vec<String>,
+
+    /// Maximum number of descriptor words kept in generated IDs
+    #[serde(default = "default_id_max_words")]
+    pub id_max_words: usize,
+
+    /// Which words are kept when an ID descriptor exceeds `id_max_words`
+    #[serde(default)]
+    pub truncation_strategy: TruncationStrategy,
+
+    /// Strip call-to-action prefixes like "Click to" and "Tap to" from ID descriptors
+    #[serde(default)]
+    pub strip_call_to_action_prefixes: bool,
+
+    /// Prefixes stripped with `strip_call_to_action_prefixes`, on top of the built-in ones
+    #[serde(default)]
+    pub call_to_action_prefixes: Vec<String>,
+
+    /// Maximum number of words kept per alias (unlimited when unset)
+    #[serde(default)]
+    pub alias_max_words: Option<usize>,
+
+    /// How IDs are derived (`"semantic"` or `"content"`)
+    #[serde(default)]
+    pub id_strategy: IdStrategy,
+
+    /// Append the element index to content-addressed IDs
+    #[serde(default)]
+    pub content_id_include_index: bool,
+
+    /// The number element indices in IDs start from (0 or 1)
+    #[serde(default = "default_index_base")]
+    pub index_base: usize,
+
+    /// Zero-pad element indices in IDs to this many digits (e.g. 2 for `-01`)
+    #[serde(default)]
+    pub index_pad_width: usize,
+
+    /// Generate IDs, types and aliases exactly like the Babel plugin, so selectors saved
+    /// against it keep working
+    #[serde(default)]
+    pub legacy_babel_compat: bool,
+
     /// Maximum number of aliases per element
     #[serde(default = "default_max_aliases")]
     pub max_aliases: usize,
 
-    /// Whether to skip elements that already have data-ui-id
+    /// Maximum byte length of the joined aliases (unlimited when unset).
+    /// Whole aliases are dropped from the end to fit.
+    #[serde(default)]
+    pub max_aliases_bytes: Option<usize>,
+
+    /// Sort aliases lexicographically (before `max_aliases` truncation) for stable output
+    #[serde(default)]
+    pub sort_aliases: bool,
+
+    /// How many levels of synonym groups to follow transitively (1 = direct synonyms only)
+    #[serde(default = "default_synonym_depth")]
+    pub synonym_depth: usize,
+
+    /// Directory of `<locale>.json` synonym files (read with the `native` feature)
+    #[serde(default)]
+    pub synonym_dir: Option<String>,
+
+    /// Locale whose synonym file in `synonym_dir` is used (e.g. `"de"`)
+    #[serde(default)]
+    pub locale: Option<String>,
+
+    /// File that receives a TypeScript union of every generated ID in the build
+    /// (written with the `native` feature)
+    #[serde(default)]
+    pub types_output_path: Option<String>,
+
+    /// Directory that receives a JSON map of each generated ID to its file, line and
+    /// component, one fragment per source file (written with the `native` feature)
+    #[serde(default)]
+    pub id_map_path: Option<String>,
+
+    /// Add the initialism of multi-word descriptors as an alias ("faq", "api")
+    #[serde(default)]
+    pub generate_acronyms: bool,
+
+    /// Split camelCase, snake_case and kebab-case identifiers (e.g. a `name` of
+    /// `submitOrder`) into words, in IDs and aliases. An identifier's words also become
+    /// aliases of their own.
+    #[serde(default)]
+    pub split_identifiers: bool,
+
+    /// Leave the ID's descriptor (e.g. "sign in") out of the aliases, keeping its synonyms
+    #[serde(default)]
+    pub alias_excludes_id_descriptor: bool,
+
+    /// Add keywords from the text of the elements an `aria-describedby` references (in the
+    /// same file) as aliases
+    #[serde(default)]
+    pub describedby_aliases: bool,
+
+    /// Use the nearest ancestor `title` as a descriptor for elements that have none
+    #[serde(default)]
+    pub inherit_ancestor_title: bool,
+
+    /// Describe and type a `<button type="submit">` (or `"reset"`) without text by its type
+    #[serde(default)]
+    pub describe_button_type: bool,
+
+    /// Type a `<button>` with `aria-haspopup` as a `menu-trigger`, or with `aria-controls`
+    /// as a `disclosure`
+    #[serde(default)]
+    pub detect_triggers: bool,
+
+    /// Whether elements are instrumented after (`"bottom_up"`) or before (`"top_down"`)
+    /// their children. Top-down lets ancestors claim IDs before their descendants.
+    #[serde(default)]
+    pub traversal_order: TraversalOrder,
+
+    /// Leave `<a>` elements without an `href` uninstrumented instead of typing them `text`
+    #[serde(default)]
+    pub skip_anchors_without_href: bool,
+
+    /// Only instrument the outermost of nested instrumentable elements that share a
+    /// descriptor (e.g. an `<a>` wrapping a `<button>`)
+    #[serde(default)]
+    pub dedupe_nested_interactive: bool,
+
+    /// Whether to skip elements that already have data-ui-id.
+    /// When off, the existing ID is replaced, and a static one is reused as the descriptor.
     #[serde(default = "default_true")]
     pub skip_existing: bool,
 
+    /// With `skip_existing`, also skip elements whose existing ID is a dynamic expression.
+    /// When off, such IDs are replaced with a generated one.
+    #[serde(default = "default_true")]
+    pub skip_existing_dynamic: bool,
+
+    /// Use an element's static `id` as its ID exactly, instead of composing an ID from it.
+    /// Author-provided ids become authoritative.
+    #[serde(default)]
+    pub use_existing_id_verbatim: bool,
+
+    /// With `use_existing_id_verbatim`, still put the ID prefix in front (`ui-email`)
+    #[serde(default)]
+    pub prefix_verbatim_ids: bool,
+
     /// Only instrument in these components (empty = all)
     #[serde(default)]
     pub only_in_components: Vec<String>,
@@ -62,9 +356,263 @@ pub struct PluginConfig {
     #[serde(default)]
     pub skip_in_components: Vec<String>,
 
+    /// When set, only descendants of elements carrying this attribute (e.g. `data-ui-scope`)
+    /// are instrumented
+    #[serde(default)]
+    pub scope_attribute: Option<String>,
+
+    /// Skip conditionally rendered elements (`{show && <button />}`, `{ok ? <a /> : null}`)
+    /// and their descendants, since they may not always be on the page
+    #[serde(default)]
+    pub skip_conditional: bool,
+
+    /// Skip elements that nothing static describes (no static text, label, id or descriptor
+    /// attribute), rather than giving them a weak positional ID
+    #[serde(default)]
+    pub skip_fully_dynamic: bool,
+
+    /// Skip elements inside an `<svg>` (icons, charts)
+    #[serde(default)]
+    pub skip_svg: bool,
+
+    /// Tags still instrumented inside an `<svg>` with `skip_svg`, even when they aren't in
+    /// `elements` (e.g. `["a", "circle"]` for interactive charts)
+    #[serde(default)]
+    pub svg_instrument_elements: Vec<String>,
+
+    /// Only instrument elements matching one of these selectors (empty = all).
+    /// Supports `tag`, `.class`, `[attr]` and `[attr=value]`, matched on static attributes.
+    #[serde(default)]
+    pub match_selectors: Vec<String>,
+
     /// Enable verbose logging
     #[serde(default)]
     pub verbose: bool,
+
+    /// Log how long each file's transform took
+    #[serde(default)]
+    pub profile: bool,
+
+    /// With `profile`, files whose transform takes longer than this many milliseconds are
+    /// reported as slow
+    #[serde(default = "default_profile_threshold_ms")]
+    pub profile_threshold_ms: u64,
+
+    /// Treat IDs without a descriptor or with a collision suffix as errors: `transform`
+    /// fails with them, and the plugin reports them
+    #[serde(default)]
+    pub fail_on_weak: bool,
+
+    /// Prepend config warnings and diagnostics to each file as a block comment
+    #[serde(default)]
+    pub warnings_as_comments: bool,
+
+    /// After transforming, check that instrumenting the output again would change nothing,
+    /// panicking otherwise. Only debug builds of the plugin run the check.
+    #[serde(default)]
+    pub self_check: bool,
+
+    /// After transforming, check that no two elements of the file carry the same ID,
+    /// warning with any duplicates (e.g. pre-instrumented elements that were skipped)
+    #[serde(default)]
+    pub validate_uniqueness: bool,
+
+    /// Fallback descriptors used when none can be inferred from the element.
+    ///
+    /// Keys are either a tag name (`"form"`) or a tag name plus the element's
+    /// glyph-only text content (`"button:×"`). The glyph form takes precedence.
+    #[serde(default = "default_descriptor_map")]
+    pub default_descriptors: HashMap<String, String>,
+
+    /// Descriptor sources to try, in order, keyed by tag name (e.g.
+    /// `{ "input": ["name", "placeholder"] }`).
+    ///
+    /// Only the listed sources are used for that tag; tags without an entry use the
+    /// built-in chain. An explicit label always comes first.
+    #[serde(default)]
+    pub descriptor_sources_by_tag: HashMap<String, Vec<DescriptorSource>>,
+
+    /// Descriptor sources to try, in order, for tags without a `descriptor_sources_by_tag`
+    /// entry (the built-in chain when unset)
+    #[serde(default)]
+    pub id_sources: Option<Vec<DescriptorSource>>,
+
+    /// Sources that become aliases, in order, independently of `id_sources`. Only `text`
+    /// (the primary alias), `aria_label`, `placeholder`, `title` and `name` are used.
+    #[serde(default = "default_alias_sources")]
+    pub alias_sources: Vec<DescriptorSource>,
+
+    /// Semantic types keyed by `className` token (e.g. `{ "nav-link": "nav-link" }`). The
+    /// first token of an element's static `className` found here sets its type, overriding
+    /// the tag-based one.
+    #[serde(default)]
+    pub classname_type_map: HashMap<String, String>,
+
+    /// Attributes injected by frameworks (e.g. styled-components' `as` or `forwardedAs`)
+    /// that are left out of ID, type and alias inference. They stay on the element.
+    #[serde(default)]
+    pub ignore_attributes_for_inference: Vec<String>,
+
+    /// Maximum number of attributes an element may hold after injection. Attributes that
+    /// would exceed it aren't injected, in injection order (id, type, ..., aliases last).
+    #[serde(default)]
+    pub max_injected_attributes: Option<usize>,
+
+    /// How injected attribute values are written (`"string"` or `"expression"`)
+    #[serde(default)]
+    pub attribute_value_style: AttributeValueStyle,
+
+    /// Where injected attributes go among the element's own (`"append"`, `"prepend"` or
+    /// `"after_static"`)
+    #[serde(default)]
+    pub attribute_placement: AttributePlacement,
+}
+
+/// Strategy used to derive element IDs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdStrategy {
+    /// Component name, file path, descriptor and element type
+    #[default]
+    Semantic,
+    /// Tag and descriptor only, so identical markup gets the same ID in any component
+    Content,
+}
+
+/// How HTML elements are told apart from components
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HtmlElementPredicate {
+    /// Any tag starting with a lowercase letter
+    #[default]
+    LowercaseFirst,
+    /// Only tags listed in `html_elements`
+    ExplicitList,
+    /// Lowercase tags without a dash; dashed tags are custom elements
+    ContainsDashIsCustom,
+}
+
+/// How an element's separate text nodes combine into one descriptor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextJoinStrategy {
+    /// All text nodes, space-separated
+    #[default]
+    Concat,
+    /// The first text node only
+    First,
+    /// The longest text node
+    Longest,
+}
+
+/// Which words of a long descriptor are kept in the ID
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationStrategy {
+    /// The first N words
+    #[default]
+    Head,
+    /// The first N-1 words and the last word
+    HeadTail,
+}
+
+/// Order in which a JSX element and its children are instrumented
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraversalOrder {
+    /// Children first, then the element
+    #[default]
+    BottomUp,
+    /// The element first, then its children
+    TopDown,
+}
+
+/// Where an element's ID descriptor or aliases can come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DescriptorSource {
+    /// The `id` attribute
+    Id,
+    /// The `data-testid` attribute
+    TestId,
+    /// Static text content
+    Text,
+    /// The `aria-label` attribute
+    AriaLabel,
+    Placeholder,
+    Title,
+    /// Text of a `<select>`'s selected (or first) option
+    Option,
+    /// The `name` attribute
+    Name,
+    /// The last path segment of a static `href`
+    Href,
+    /// The `value` attribute
+    Value,
+    /// The `label` attribute of an `<optgroup>`
+    GroupLabel,
+    /// The `alt` text of an image
+    Alt,
+}
+
+/// How the id, type and aliases are emitted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmitMode {
+    /// One attribute each: `data-ui-id`, `data-ui-type`, `data-ui-aliases`
+    #[default]
+    Separate,
+    /// A single attribute: `data-ui="id|type|alias1,alias2"`
+    Compact,
+}
+
+/// The kind of build being instrumented
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    Development,
+    Production,
+}
+
+/// An attribute the plugin can emit on an instrumented element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InjectedAttribute {
+    Id,
+    Type,
+    Aliases,
+    Role,
+    Text,
+    Group,
+    Primary,
+    Portal,
+    Confidence,
+    IndexKey,
+}
+
+/// How injected attribute values are emitted in JSX
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttributeValueStyle {
+    /// Plain string literal: `data-ui-id="..."`
+    #[default]
+    String,
+    /// String literal wrapped in an expression container: `data-ui-id={"..."}`
+    Expression,
+}
+
+/// Where injected attributes are placed among an element's existing attributes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributePlacement {
+    /// After all existing attributes
+    #[default]
+    Append,
+    /// Before all existing attributes
+    Prepend,
+    /// Right after the last `className`, `class`, `id` or `style` attribute (first when
+    /// there is none), so they come before event handlers
+    AfterStatic,
 }
 
 fn default_elements() -> Vec<String> {
@@ -78,6 +626,21 @@ fn default_elements() -> Vec<String> {
     ]
 }
 
+fn default_alias_sources() -> Vec<DescriptorSource> {
+    vec![
+        DescriptorSource::Text,
+        DescriptorSource::AriaLabel,
+        DescriptorSource::Placeholder,
+        DescriptorSource::Title,
+        DescriptorSource::Name,
+        DescriptorSource::Alt,
+    ]
+}
+
+fn default_separator() -> String {
+    "-".into()
+}
+
 fn default_id_prefix() -> String {
     "ui".into()
 }
@@ -94,46 +657,257 @@ fn default_type_attribute() -> String {
     "data-ui-type".into()
 }
 
+fn default_role_attribute() -> String {
+    "data-ui-role".into()
+}
+
+fn default_text_attribute() -> String {
+    "data-ui-text".into()
+}
+
+fn default_index_key_attribute() -> String {
+    "data-ui-index-key".into()
+}
+
+fn default_confidence_attribute() -> String {
+    "data-ui-confidence".into()
+}
+
+fn default_portal_attribute() -> String {
+    "data-ui-portal".into()
+}
+
+fn default_primary_attribute() -> String {
+    "data-ui-primary".into()
+}
+
+fn default_group_attribute() -> String {
+    "data-ui-group".into()
+}
+
+fn default_compact_attribute() -> String {
+    "data-ui".into()
+}
+
+fn default_production_attributes() -> Vec<InjectedAttribute> {
+    vec![InjectedAttribute::Id]
+}
+
+fn default_compact_delimiter() -> String {
+    "|".into()
+}
+
+fn default_label_attribute() -> String {
+    "data-ui-label".into()
+}
+
+fn default_ignore_attribute() -> String {
+    "data-ui-ignore".into()
+}
+
 fn default_true() -> bool {
     true
 }
 
+fn default_index_base() -> usize {
+    1
+}
+
 fn default_max_aliases() -> usize {
     5
 }
 
+fn default_id_max_words() -> usize {
+    4
+}
+
+fn default_synonym_depth() -> usize {
+    1
+}
+
+fn default_profile_threshold_ms() -> u64 {
+    50
+}
+
+fn default_descriptor_map() -> HashMap<String, String> {
+    ["×", "✕", "✖"]
+        .iter()
+        .map(|glyph| (format!("button:{}", glyph), "close".to_string()))
+        .collect()
+}
+
 impl Default for PluginConfig {
     fn default() -> Self {
         Self {
+            enabled: true,
             elements: default_elements(),
+            additional_elements: vec![],
+            html_element_predicate: HtmlElementPredicate::default(),
+            html_elements: vec![],
+            detect_contenteditable: false,
             id_prefix: default_id_prefix(),
+            component_prefixes: HashMap::new(),
+            id_separator: default_separator(),
+            word_separator: default_separator(),
             id_attribute: default_id_attribute(),
             aliases_attribute: default_aliases_attribute(),
             type_attribute: default_type_attribute(),
+            role_attribute: default_role_attribute(),
+            emit_role_attribute: false,
+            text_attribute: default_text_attribute(),
+            emit_text_attribute: false,
+            index_key_attribute: default_index_key_attribute(),
+            emit_index_key: false,
+            confidence_attribute: default_confidence_attribute(),
+            emit_confidence: false,
+            primary_attribute: default_primary_attribute(),
+            mark_primary_actions: false,
+            portal_attribute: default_portal_attribute(),
+            portal_component_hint: false,
+            group_attribute: default_group_attribute(),
+            emit_mode: EmitMode::default(),
+            compact_attribute: default_compact_attribute(),
+            compact_delimiter: default_compact_delimiter(),
+            environment: None,
+            production_attributes: default_production_attributes(),
+            mirror_to_id: false,
+            label_attribute: default_label_attribute(),
+            ignore_attribute: default_ignore_attribute(),
+            text_join_strategy: TextJoinStrategy::default(),
+            derive_from_child_aria: false,
+            extract_inner_html: false,
             generate_aliases: true,
             include_component_name: true,
+            children_inherit_parent_component: true,
+            max_component_depth: None,
             include_file_path: false,
             hash_ids: false,
-            max_aliases: 5,
+            guarantee_global_unique: false,
+            reserved_ids: vec![],
+            id_max_words: 4,
+            truncation_strategy: TruncationStrategy::default(),
+            strip_call_to_action_prefixes: false,
+            call_to_action_prefixes: vec![],
+            alias_max_words: None,
+            id_strategy: IdStrategy::default(),
+            content_id_include_index: false,
+            index_base: default_index_base(),
+            index_pad_width: 0,
+            legacy_babel_compat: false,
+            max_aliases: default_max_aliases(),
+            max_aliases_bytes: None,
+            sort_aliases: false,
+            synonym_depth: 1,
+            synonym_dir: None,
+            locale: None,
+            types_output_path: None,
+            id_map_path: None,
+            generate_acronyms: false,
+            split_identifiers: false,
+            alias_excludes_id_descriptor: false,
+            describedby_aliases: false,
+            inherit_ancestor_title: false,
+            describe_button_type: false,
+            detect_triggers: false,
+            traversal_order: TraversalOrder::default(),
+            skip_anchors_without_href: false,
+            dedupe_nested_interactive: false,
             skip_existing: true,
+            skip_existing_dynamic: true,
+            use_existing_id_verbatim: false,
+            prefix_verbatim_ids: false,
             only_in_components: vec![],
             skip_in_components: vec![],
+            scope_attribute: None,
+            skip_conditional: false,
+            skip_fully_dynamic: false,
+            skip_svg: false,
+            svg_instrument_elements: vec![],
+            match_selectors: vec![],
             verbose: false,
+            profile: false,
+            profile_threshold_ms: default_profile_threshold_ms(),
+            fail_on_weak: false,
+            warnings_as_comments: false,
+            self_check: false,
+            validate_uniqueness: false,
+            default_descriptors: default_descriptor_map(),
+            descriptor_sources_by_tag: HashMap::new(),
+            id_sources: None,
+            alias_sources: default_alias_sources(),
+            classname_type_map: HashMap::new(),
+            ignore_attributes_for_inference: vec![],
+            max_injected_attributes: None,
+            attribute_value_style: AttributeValueStyle::default(),
+            attribute_placement: AttributePlacement::default(),
         }
     }
 }
 
 impl PluginConfig {
+    /// Start building a config from the defaults
+    pub fn builder() -> PluginConfigBuilder {
+        PluginConfigBuilder::default()
+    }
+
     /// Check if an element type should be instrumented
     pub fn should_instrument(&self, tag_name: &str) -> bool {
-        self.elements.iter().any(|e| e == tag_name)
+        self.elements
+            .iter()
+            .chain(&self.additional_elements)
+            .any(|e| e == tag_name)
+    }
+
+    /// Whether `attribute` is emitted, as its own option says, overridden by `environment`
+    pub fn emits(&self, attribute: InjectedAttribute) -> bool {
+        let enabled = match attribute {
+            InjectedAttribute::Id | InjectedAttribute::Type | InjectedAttribute::Group => true,
+            InjectedAttribute::Aliases => self.generate_aliases,
+            InjectedAttribute::Role => self.emit_role_attribute,
+            InjectedAttribute::Text => self.emit_text_attribute,
+            InjectedAttribute::Primary => self.mark_primary_actions,
+            InjectedAttribute::Portal => self.portal_component_hint,
+            InjectedAttribute::Confidence => self.emit_confidence,
+            InjectedAttribute::IndexKey => self.emit_index_key,
+        };
+        match self.environment {
+            None => enabled,
+            Some(Environment::Development) => true,
+            Some(Environment::Production) => {
+                enabled && self.production_attributes.contains(&attribute)
+            }
+        }
+    }
+
+    /// The element types that will be instrumented: `elements` plus `additional_elements`,
+    /// in order and without duplicates
+    ///
+    /// Elements instrumented for being `contenteditable` aren't listed, since any tag can be.
+    pub fn effective_elements(&self) -> Vec<String> {
+        let mut elements: Vec<String> = vec![];
+        for element in self.elements.iter().chain(&self.additional_elements) {
+            if !elements.contains(element) {
+                elements.push(element.clone());
+            }
+        }
+        elements
     }
 
     /// Check if we should skip based on component name
-    pub fn should_skip_component(&self, component_name: Option<&str>) -> bool {
+    ///
+    /// `skip_in_components` entries may be scoped to a tag: `"Foo:button"` skips only
+    /// buttons in `Foo`, while `"Foo"` skips every element in it.
+    pub fn should_skip_component(&self, component_name: Option<&str>, tag_name: &str) -> bool {
         if let Some(name) = component_name {
             // Check skip list
-            if self.skip_in_components.iter().any(|c| c == name) {
+            let skipped = self
+                .skip_in_components
+                .iter()
+                .any(|entry| match entry.split_once(':') {
+                    Some((component, tag)) => component == name && tag == tag_name,
+                    None => entry == name,
+                });
+            if skipped {
                 return true;
             }
 
@@ -152,12 +926,472 @@ impl PluginConfig {
 
         false
     }
+
+    /// Check the configuration for problems, returning a warning for each
+    pub fn validate(&self) -> Vec<ConfigWarning> {
+        let mut warnings = vec![];
+
+        for (option, name) in [
+            ("idAttribute", &self.id_attribute),
+            ("aliasesAttribute", &self.aliases_attribute),
+            ("typeAttribute", &self.type_attribute),
+            ("roleAttribute", &self.role_attribute),
+            ("textAttribute", &self.text_attribute),
+            ("indexKeyAttribute", &self.index_key_attribute),
+            ("confidenceAttribute", &self.confidence_attribute),
+            ("primaryAttribute", &self.primary_attribute),
+            ("portalAttribute", &self.portal_attribute),
+            ("groupAttribute", &self.group_attribute),
+            ("compactAttribute", &self.compact_attribute),
+            ("labelAttribute", &self.label_attribute),
+            ("ignoreAttribute", &self.ignore_attribute),
+        ] {
+            if !is_valid_attribute_name(name) {
+                warnings.push(ConfigWarning::option(
+                    option,
+                    format!(
+                        "{} \"{}\" should be a lowercase attribute name ([a-z][a-z0-9-]*, \
+                         optionally namespaced like ui:id); the DOM lowercases attribute \
+                         names, which breaks selectors",
+                        option, name
+                    ),
+                ));
+            } else if HTML_ATTRIBUTES.contains(&name.as_str()) {
+                warnings.push(ConfigWarning::option(
+                    option,
+                    format!(
+                        "{} \"{}\" is a standard HTML attribute; the plugin would overwrite \
+                         or misread the element's own value",
+                        option, name
+                    ),
+                ));
+            } else if name.contains(':') {
+                warnings.push(ConfigWarning::option(
+                    option,
+                    format!(
+                        "{} \"{}\" is namespaced; the React JSX transform rejects namespaced \
+                         attribute names unless it's configured with `throwIfNamespace: false`",
+                        option, name
+                    ),
+                ));
+            }
+        }
+
+        for selector in &self.match_selectors {
+            if Selector::parse(selector).is_none() {
+                warnings.push(ConfigWarning::option(
+                    "matchSelectors",
+                    format!(
+                        "matchSelectors \"{}\" is not a supported selector (tag, .class, [attr] or [attr=value]); it never matches",
+                        selector
+                    ),
+                ));
+            }
+        }
+
+        if self.synonym_dir.is_some() && cfg!(not(feature = "native")) {
+            warnings.push(ConfigWarning::option(
+                "synonymDir",
+                "synonymDir is only read by builds with the `native` feature; it is ignored here",
+            ));
+        }
+        if self.types_output_path.is_some() && cfg!(not(feature = "native")) {
+            warnings.push(ConfigWarning::option(
+                "typesOutputPath",
+                "typesOutputPath is only written by builds with the `native` feature; it is ignored here",
+            ));
+        }
+        if self.id_map_path.is_some() && cfg!(not(feature = "native")) {
+            warnings.push(ConfigWarning::option(
+                "idMapPath",
+                "idMapPath is only written by builds with the `native` feature; it is ignored here",
+            ));
+        }
+
+        if self.index_base > 1 {
+            warnings.push(ConfigWarning::option(
+                "indexBase",
+                format!("indexBase {} should be 0 or 1; 1 is used", self.index_base),
+            ));
+        }
+
+        warnings
+    }
+
+    /// Format an element's one-based index for an ID, following `index_base` and
+    /// `index_pad_width`
+    pub fn format_index(&self, element_index: usize) -> String {
+        let index = element_index - 1 + self.index_base.min(1);
+        format!("{:0width$}", index, width = self.index_pad_width)
+    }
+
+    /// The ID prefix for elements rendered in a component, falling back to `id_prefix`
+    pub fn id_prefix_for(&self, component_name: Option<&str>) -> &str {
+        component_name
+            .and_then(|name| self.component_prefixes.get(name))
+            .unwrap_or(&self.id_prefix)
+    }
+
+    /// Look up the fallback descriptor for a tag, preferring a glyph-specific entry
+    pub fn default_descriptor(&self, tag_name: &str, text_content: Option<&str>) -> Option<&str> {
+        text_content
+            .and_then(|text| {
+                self.default_descriptors
+                    .get(&format!("{}:{}", tag_name, text.trim()))
+            })
+            .or_else(|| self.default_descriptors.get(tag_name))
+            .map(|s| s.as_str())
+    }
+}
+
+/// Builds a [`PluginConfig`] fluently, for use as a library
+///
+/// Options left unset keep their defaults:
+///
+/// ```
+/// use ui_bridge_swc_plugin::config::PluginConfig;
+///
+/// let config = PluginConfig::builder()
+///     .elements(["button", "a"])
+///     .id_prefix("app")
+///     .hash_ids(true)
+///     .build();
+/// assert_eq!(config.id_prefix, "app");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PluginConfigBuilder {
+    config: PluginConfig,
+}
+
+/// One setter per option, documented by a link to the option
+macro_rules! setters {
+    (
+        values { $($field:ident: $ty:ty,)* }
+        lists { $($list:ident,)* }
+        optional_strings { $($optional:ident,)* }
+    ) => {
+        impl PluginConfigBuilder {
+            $(
+                #[doc = concat!("Set [`PluginConfig::", stringify!($field), "`]")]
+                pub fn $field(mut self, $field: impl Into<$ty>) -> Self {
+                    self.config.$field = $field.into();
+                    self
+                }
+            )*
+            $(
+                #[doc = concat!("Set [`PluginConfig::", stringify!($list), "`]")]
+                pub fn $list(mut self, $list: impl IntoIterator<Item = impl Into<String>>) -> Self {
+                    self.config.$list = $list.into_iter().map(Into::into).collect();
+                    self
+                }
+            )*
+            $(
+                #[doc = concat!("Set [`PluginConfig::", stringify!($optional), "`]")]
+                pub fn $optional(mut self, $optional: impl Into<String>) -> Self {
+                    self.config.$optional = Some($optional.into());
+                    self
+                }
+            )*
+        }
+    };
+}
+
+// Every option needs an entry here, in the group matching its setter's argument
+setters! {
+    values {
+        enabled: bool,
+        html_element_predicate: HtmlElementPredicate,
+        detect_contenteditable: bool,
+        id_prefix: String,
+        component_prefixes: HashMap<String, String>,
+        id_separator: String,
+        word_separator: String,
+        id_attribute: String,
+        aliases_attribute: String,
+        type_attribute: String,
+        role_attribute: String,
+        emit_role_attribute: bool,
+        text_attribute: String,
+        emit_text_attribute: bool,
+        index_key_attribute: String,
+        emit_index_key: bool,
+        confidence_attribute: String,
+        emit_confidence: bool,
+        primary_attribute: String,
+        mark_primary_actions: bool,
+        portal_attribute: String,
+        portal_component_hint: bool,
+        group_attribute: String,
+        label_attribute: String,
+        ignore_attribute: String,
+        emit_mode: EmitMode,
+        compact_attribute: String,
+        compact_delimiter: String,
+        environment: Option<Environment>,
+        production_attributes: Vec<InjectedAttribute>,
+        mirror_to_id: bool,
+        text_join_strategy: TextJoinStrategy,
+        derive_from_child_aria: bool,
+        extract_inner_html: bool,
+        generate_aliases: bool,
+        include_component_name: bool,
+        children_inherit_parent_component: bool,
+        max_component_depth: Option<usize>,
+        include_file_path: bool,
+        hash_ids: bool,
+        guarantee_global_unique: bool,
+        id_max_words: usize,
+        truncation_strategy: TruncationStrategy,
+        strip_call_to_action_prefixes: bool,
+        alias_max_words: Option<usize>,
+        id_strategy: IdStrategy,
+        content_id_include_index: bool,
+        index_base: usize,
+        index_pad_width: usize,
+        legacy_babel_compat: bool,
+        max_aliases: usize,
+        max_aliases_bytes: Option<usize>,
+        sort_aliases: bool,
+        synonym_depth: usize,
+        generate_acronyms: bool,
+        split_identifiers: bool,
+        alias_excludes_id_descriptor: bool,
+        describedby_aliases: bool,
+        inherit_ancestor_title: bool,
+        describe_button_type: bool,
+        detect_triggers: bool,
+        traversal_order: TraversalOrder,
+        skip_anchors_without_href: bool,
+        dedupe_nested_interactive: bool,
+        skip_existing: bool,
+        skip_existing_dynamic: bool,
+        use_existing_id_verbatim: bool,
+        prefix_verbatim_ids: bool,
+        skip_conditional: bool,
+        skip_fully_dynamic: bool,
+        skip_svg: bool,
+        verbose: bool,
+        profile: bool,
+        profile_threshold_ms: u64,
+        fail_on_weak: bool,
+        warnings_as_comments: bool,
+        self_check: bool,
+        validate_uniqueness: bool,
+        default_descriptors: HashMap<String, String>,
+        descriptor_sources_by_tag: HashMap<String, Vec<DescriptorSource>>,
+        id_sources: Option<Vec<DescriptorSource>>,
+        alias_sources: Vec<DescriptorSource>,
+        classname_type_map: HashMap<String, String>,
+        max_injected_attributes: Option<usize>,
+        attribute_value_style: AttributeValueStyle,
+        attribute_placement: AttributePlacement,
+    }
+    lists {
+        elements,
+        additional_elements,
+        html_elements,
+        reserved_ids,
+        call_to_action_prefixes,
+        only_in_components,
+        skip_in_components,
+        svg_instrument_elements,
+        match_selectors,
+        ignore_attributes_for_inference,
+    }
+    optional_strings {
+        synonym_dir,
+        locale,
+        types_output_path,
+        id_map_path,
+        scope_attribute,
+    }
+}
+
+impl PluginConfigBuilder {
+    /// The configured options
+    pub fn build(self) -> PluginConfig {
+        self.config
+    }
+}
+
+/// A problem found in the plugin options
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigWarning {
+    /// The option the warning is about, as named in the options object (e.g. `idAttribute`)
+    pub option: Option<String>,
+    pub message: String,
+}
+
+impl ConfigWarning {
+    fn option(option: &str, message: impl Into<String>) -> Self {
+        Self {
+            option: Some(option.to_string()),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Environment variable holding JSON options, read when the host passes none
+pub const CONFIG_ENV_VAR: &str = "UI_BRIDGE_CONFIG";
+
+/// The raw options to resolve: the host's, or `UI_BRIDGE_CONFIG` when it passes none
+/// (nothing, `{}` or `null`)
+///
+/// The SWC WASM sandbox usually doesn't expose the host's environment, so the variable
+/// is reliably seen only by native builds.
+pub fn options_or_env(raw: Option<String>) -> String {
+    options_or(raw, || std::env::var(CONFIG_ENV_VAR).ok())
+}
+
+/// The raw options to resolve: the host's, or the ones `fallback` gives when it passes none
+fn options_or(raw: Option<String>, fallback: impl FnOnce() -> Option<String>) -> String {
+    let raw = raw.unwrap_or_default();
+    let is_empty = raw.trim().is_empty()
+        || serde_json::from_str::<serde_json::Value>(&raw).is_ok_and(|value| {
+            value.is_null() || value.as_object().is_some_and(|object| object.is_empty())
+        });
+    if !is_empty {
+        return raw;
+    }
+
+    fallback()
+        .filter(|fallback| !fallback.trim().is_empty())
+        .unwrap_or(raw)
+}
+
+/// Parse raw plugin options (JSON) into the effective configuration, with any warnings
+///
+/// Options that fail to parse fall back to the defaults, with a warning saying why.
+/// Empty options mean the defaults.
+pub fn resolve_config(raw: &str) -> (PluginConfig, Vec<ConfigWarning>) {
+    let raw = if raw.trim().is_empty() { "{}" } else { raw };
+
+    match serde_json::from_str::<PluginConfig>(raw) {
+        Ok(config) => {
+            let mut warnings = unknown_options(raw);
+            warnings.extend(config.validate());
+            (config, warnings)
+        }
+        Err(err) => (
+            PluginConfig::default(),
+            vec![ConfigWarning {
+                option: None,
+                message: format!("invalid options, using the defaults: {}", err),
+            }],
+        ),
+    }
+}
+
+/// Warn about the top-level options the plugin doesn't know, which parsing ignores
+///
+/// There are no presets, so `preset` and `extends` get a warning of their own rather
+/// than being dropped silently.
+fn unknown_options(raw: &str) -> Vec<ConfigWarning> {
+    let Ok(serde_json::Value::Object(options)) = serde_json::from_str(raw) else {
+        return vec![];
+    };
+    // Every option is serialized, so the defaults name them all
+    let known = serde_json::to_value(PluginConfig::default()).unwrap_or_default();
+    options
+        .keys()
+        .filter(|key| known.get(key.as_str()).is_none())
+        .map(|key| {
+            let message = match key.as_str() {
+                "preset" | "extends" => format!(
+                    "{} is not supported, set the options directly; it is ignored",
+                    key
+                ),
+                _ => format!("unknown option \"{}\"; it is ignored", key),
+            };
+            ConfigWarning::option(key, message)
+        })
+        .collect()
+}
+
+/// Standard HTML attributes, which can't hold the plugin's own values
+const HTML_ATTRIBUTES: &[&str] = &[
+    "id",
+    "class",
+    "title",
+    "name",
+    "type",
+    "role",
+    "style",
+    "lang",
+    "dir",
+    "hidden",
+    "value",
+    "href",
+    "src",
+    "alt",
+    "for",
+    "form",
+    "label",
+    "placeholder",
+    "tabindex",
+    "slot",
+    "is",
+];
+
+/// Check that an attribute name is lowercase (`[a-z][a-z0-9-]*`, not ending in `-`), or two
+/// such names joined by a namespace colon (`ui:id`)
+///
+/// Besides `data-*` attributes, this allows other namespaces (`x-ui-id`, `ui:id`).
+fn is_valid_attribute_name(name: &str) -> bool {
+    let is_valid_part = |part: &str| {
+        part.starts_with(|c: char| c.is_ascii_lowercase())
+            && !part.ends_with('-')
+            && part
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    };
+    match name.split_once(':') {
+        Some((ns, local)) => is_valid_part(ns) && is_valid_part(local),
+        None => is_valid_part(name),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_builder() {
+        let built = PluginConfig::builder()
+            .elements(["button", "a"])
+            .id_prefix("app")
+            .hash_ids(true)
+            .max_injected_attributes(8)
+            .types_output_path("src/ui-ids.d.ts")
+            .emit_mode(EmitMode::Compact)
+            .build();
+        let literal = PluginConfig {
+            elements: vec!["button".into(), "a".into()],
+            id_prefix: "app".into(),
+            hash_ids: true,
+            max_injected_attributes: Some(8),
+            types_output_path: Some("src/ui-ids.d.ts".into()),
+            emit_mode: EmitMode::Compact,
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&built).unwrap(),
+            serde_json::to_value(&literal).unwrap()
+        );
+
+        // Nothing set gives the defaults
+        assert_eq!(
+            serde_json::to_value(PluginConfig::builder().build()).unwrap(),
+            serde_json::to_value(PluginConfig::default()).unwrap()
+        );
+    }
+
     #[test]
     fn test_default_config() {
         let config = PluginConfig::default();
@@ -175,23 +1409,85 @@ mod tests {
         assert!(!config.should_instrument("span"));
     }
 
+    #[test]
+    fn test_additional_elements() {
+        let json = r#"{ "additionalElements": ["dialog"] }"#;
+        let config: PluginConfig = serde_json::from_str(json).unwrap();
+        for tag in [
+            "button", "input", "select", "textarea", "a", "form", "dialog",
+        ] {
+            assert!(
+                config.should_instrument(tag),
+                "{} should be instrumented",
+                tag
+            );
+        }
+        assert!(!config.should_instrument("div"));
+
+        // `elements` still overrides the defaults, additions are applied on top
+        let json = r#"{ "elements": ["button"], "additionalElements": ["dialog"] }"#;
+        let config: PluginConfig = serde_json::from_str(json).unwrap();
+        assert!(config.should_instrument("button"));
+        assert!(config.should_instrument("dialog"));
+        assert!(!config.should_instrument("input"));
+    }
+
+    #[test]
+    fn test_effective_elements() {
+        assert_eq!(
+            PluginConfig::default().effective_elements(),
+            ["button", "input", "select", "textarea", "a", "form"]
+        );
+
+        let json = r#"{ "additionalElements": ["dialog", "a", "summary"] }"#;
+        let config: PluginConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.effective_elements(),
+            ["button", "input", "select", "textarea", "a", "form", "dialog", "summary"]
+        );
+        assert!(config
+            .effective_elements()
+            .iter()
+            .all(|tag| config.should_instrument(tag)));
+
+        let json = r#"{ "elements": ["button"], "additionalElements": ["dialog"] }"#;
+        let config: PluginConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.effective_elements(), ["button", "dialog"]);
+    }
+
     #[test]
     fn test_should_skip_component() {
         let mut config = PluginConfig::default();
 
         // No restrictions
-        assert!(!config.should_skip_component(Some("MyComponent")));
+        assert!(!config.should_skip_component(Some("MyComponent"), "button"));
 
         // Skip specific component
         config.skip_in_components = vec!["SkipMe".into()];
-        assert!(config.should_skip_component(Some("SkipMe")));
-        assert!(!config.should_skip_component(Some("DontSkip")));
+        assert!(config.should_skip_component(Some("SkipMe"), "button"));
+        assert!(!config.should_skip_component(Some("DontSkip"), "button"));
 
         // Only in specific components
         config.skip_in_components = vec![];
         config.only_in_components = vec!["OnlyThis".into()];
-        assert!(!config.should_skip_component(Some("OnlyThis")));
-        assert!(config.should_skip_component(Some("NotThis")));
+        assert!(!config.should_skip_component(Some("OnlyThis"), "button"));
+        assert!(config.should_skip_component(Some("NotThis"), "button"));
+    }
+
+    #[test]
+    fn test_scoped_skip_in_components() {
+        let config = PluginConfig {
+            skip_in_components: vec!["Foo:button".into(), "Bar".into(), "Baz:my:widget".into()],
+            ..Default::default()
+        };
+
+        assert!(config.should_skip_component(Some("Foo"), "button"));
+        assert!(!config.should_skip_component(Some("Foo"), "a"));
+        assert!(!config.should_skip_component(Some("Other"), "button"));
+        assert!(config.should_skip_component(Some("Bar"), "button"));
+        assert!(config.should_skip_component(Some("Bar"), "a"));
+        assert!(config.should_skip_component(Some("Baz"), "my:widget"));
+        assert!(!config.should_skip_component(None, "button"));
     }
 
     #[test]
@@ -208,5 +1504,217 @@ mod tests {
         assert!(!config.generate_aliases);
         // Defaults should be applied
         assert!(config.include_component_name);
+        assert_eq!(config.attribute_value_style, AttributeValueStyle::String);
+    }
+
+    #[test]
+    fn test_component_prefixes() {
+        let json = r#"{ "idPrefix": "app", "componentPrefixes": { "CheckoutForm": "checkout" } }"#;
+        let config: PluginConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.id_prefix_for(Some("CheckoutForm")), "checkout");
+        assert_eq!(config.id_prefix_for(Some("LoginForm")), "app");
+        assert_eq!(config.id_prefix_for(None), "app");
+    }
+
+    #[test]
+    fn test_emits_for_environment() {
+        let config = PluginConfig::default();
+        assert!(config.emits(InjectedAttribute::Aliases));
+        assert!(!config.emits(InjectedAttribute::Text));
+
+        let json = r#"{ "environment": "development", "generateAliases": false }"#;
+        let config: PluginConfig = serde_json::from_str(json).unwrap();
+        assert!(config.emits(InjectedAttribute::Aliases));
+        assert!(config.emits(InjectedAttribute::Text));
+
+        let json = r#"{
+            "environment": "production",
+            "productionAttributes": ["id", "text", "index_key"],
+            "emitIndexKey": false
+        }"#;
+        let config: PluginConfig = serde_json::from_str(json).unwrap();
+        assert!(config.emits(InjectedAttribute::Id));
+        assert!(!config.emits(InjectedAttribute::Type));
+        assert!(!config.emits(InjectedAttribute::Aliases));
+        // Listing an attribute doesn't enable it
+        assert!(!config.emits(InjectedAttribute::Text));
+        assert!(!config.emits(InjectedAttribute::IndexKey));
+    }
+
+    #[test]
+    fn test_format_index() {
+        let config = PluginConfig::default();
+        assert_eq!(config.format_index(1), "1");
+        assert_eq!(config.format_index(12), "12");
+
+        let json = r#"{ "indexBase": 0, "indexPadWidth": 2 }"#;
+        let config: PluginConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.format_index(1), "00");
+        assert_eq!(config.format_index(12), "11");
+        assert_eq!(config.format_index(101), "100");
+
+        let config = PluginConfig {
+            index_base: 2,
+            ..Default::default()
+        };
+        assert_eq!(config.format_index(1), "1");
+        assert_eq!(config.validate()[0].option.as_deref(), Some("indexBase"));
+    }
+
+    #[test]
+    fn test_deserialize_attribute_placement() {
+        let json = r#"{ "attributePlacement": "after_static" }"#;
+        let config: PluginConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.attribute_placement, AttributePlacement::AfterStatic);
+        assert_eq!(
+            PluginConfig::default().attribute_placement,
+            AttributePlacement::Append
+        );
+    }
+
+    #[test]
+    fn test_deserialize_attribute_value_style() {
+        let json = r#"{ "attributeValueStyle": "expression" }"#;
+        let config: PluginConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.attribute_value_style,
+            AttributeValueStyle::Expression
+        );
+    }
+
+    #[test]
+    fn test_resolve_config() {
+        let (config, warnings) = resolve_config(
+            r#"{ "idPrefix": "app", "emitMode": "compact", "compactAttribute": "data-UI" }"#,
+        );
+        assert_eq!(config.id_prefix, "app");
+        assert_eq!(config.emit_mode, EmitMode::Compact);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].option.as_deref(), Some("compactAttribute"));
+        assert!(warnings[0].to_string().contains("\"data-UI\""));
+
+        let (config, warnings) = resolve_config("");
+        assert_eq!(config.id_prefix, "ui");
+        assert!(warnings.is_empty());
+
+        // Unknown options, presets included, aren't dropped silently
+        let (config, warnings) = resolve_config(
+            r#"{ "preset": "strict", "idAttribute": "ID", "idPrefx": "app", "idPrefix": "app" }"#,
+        );
+        assert_eq!(config.id_prefix, "app");
+        assert_eq!(config.id_attribute, "ID");
+        let options: Vec<_> = warnings.iter().map(|w| w.option.as_deref()).collect();
+        assert_eq!(
+            options,
+            [Some("idPrefx"), Some("preset"), Some("idAttribute")]
+        );
+        assert_eq!(
+            warnings[0].message,
+            r#"unknown option "idPrefx"; it is ignored"#
+        );
+        assert!(warnings[1].message.starts_with("preset is not supported"));
+        assert!(warnings[2]
+            .message
+            .contains(r#"idAttribute "ID" should be a lowercase"#));
+
+        let (config, warnings) = resolve_config(r#"{ "idPrefix": 42 }"#);
+        assert_eq!(config.id_prefix, "ui");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].option, None);
+        assert!(warnings[0].message.starts_with("invalid options"));
+    }
+
+    #[test]
+    fn test_options_from_env() {
+        let unset = || None;
+        assert_eq!(options_or(None, unset), "");
+        assert_eq!(options_or(Some("{}".into()), unset), "{}");
+
+        let env = || Some(r#"{ "idPrefix": "ci" }"#.to_string());
+        for raw in [None, Some(""), Some(" { } "), Some("null")] {
+            let (config, warnings) = resolve_config(&options_or(raw.map(String::from), env));
+            assert_eq!(config.id_prefix, "ci", "{:?}", raw);
+            assert!(warnings.is_empty());
+        }
+
+        // Options from the host win
+        let (config, _) = resolve_config(&options_or(Some(r#"{ "idPrefix": "app" }"#.into()), env));
+        assert_eq!(config.id_prefix, "app");
+
+        // An empty variable is the same as an unset one
+        assert_eq!(options_or(Some("{}".into()), || Some(" ".into())), "{}");
+    }
+
+    #[test]
+    fn test_validate_attribute_names() {
+        let config = PluginConfig::default();
+        assert!(config.validate().is_empty());
+
+        let config = PluginConfig {
+            id_attribute: "data-UIid".into(),
+            type_attribute: "ui_type".into(),
+            ..Default::default()
+        };
+        let warnings = config.validate();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].message.contains("idAttribute \"data-UIid\""));
+        assert!(warnings[1].message.contains("typeAttribute \"ui_type\""));
+
+        // Other namespaces than data-* are fine, though ns:name needs throwIfNamespace: false
+        let config = PluginConfig {
+            id_attribute: "ui:id".into(),
+            type_attribute: "x-ui-type".into(),
+            ..Default::default()
+        };
+        let warnings = config.validate();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].option.as_deref(), Some("idAttribute"));
+        assert!(warnings[0].message.contains("throwIfNamespace: false"));
+
+        // Standard HTML attributes are rejected
+        let config = PluginConfig {
+            id_attribute: "id".into(),
+            label_attribute: "title".into(),
+            ..Default::default()
+        };
+        let warnings = config.validate();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0]
+            .message
+            .contains("idAttribute \"id\" is a standard HTML attribute"));
+        assert!(warnings[1].message.contains("labelAttribute \"title\""));
+
+        let config = PluginConfig {
+            match_selectors: vec!["button.primary".into(), "nav > a".into()],
+            ..Default::default()
+        };
+        let warnings = config.validate();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].option.as_deref(), Some("matchSelectors"));
+        assert!(warnings[0].message.contains("\"nav > a\""));
+
+        assert!(is_valid_attribute_name("data-test-id2"));
+        assert!(!is_valid_attribute_name("data-"));
+        assert!(!is_valid_attribute_name("data-ui_id"));
+        assert!(!is_valid_attribute_name("ui:"));
+        assert!(!is_valid_attribute_name("ui:id:x"));
+        assert!(!is_valid_attribute_name("2fa"));
+    }
+
+    #[test]
+    fn test_default_descriptor() {
+        let mut config = PluginConfig::default();
+        assert_eq!(
+            config.default_descriptor("button", Some("×")),
+            Some("close")
+        );
+        assert_eq!(config.default_descriptor("button", Some("Save")), None);
+        assert_eq!(config.default_descriptor("form", None), None);
+
+        config
+            .default_descriptors
+            .insert("form".into(), "form".into());
+        assert_eq!(config.default_descriptor("form", None), Some("form"));
     }
 }
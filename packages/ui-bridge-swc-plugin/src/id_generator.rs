@@ -2,10 +2,22 @@
 //!
 //! Generates deterministic, semantic IDs for UI elements.
 
-use crate::config::PluginConfig;
+use crate::config::{DescriptorSource, IdStrategy, PluginConfig, TruncationStrategy};
+use crate::name_types::infer_type_from_name;
+use std::borrow::Cow;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
+/// Call-to-action prefixes stripped from descriptors with `strip_call_to_action_prefixes`
+const CALL_TO_ACTION_PREFIXES: &[&str] = &[
+    "click here to",
+    "tap here to",
+    "click to",
+    "tap to",
+    "press to",
+    "touch to",
+];
+
 /// Context for generating an element ID
 #[derive(Debug, Default)]
 pub struct IdContext<'a> {
@@ -17,70 +29,445 @@ pub struct IdContext<'a> {
     pub placeholder: Option<&'a str>,
     pub title: Option<&'a str>,
     pub existing_id: Option<&'a str>,
+    /// Value of the element's `data-testid`
+    pub test_id: Option<&'a str>,
+    /// Explicit descriptor from the label attribute (e.g. `data-ui-label`)
+    pub label: Option<&'a str>,
+    /// Text of a `<select>`'s selected (or first) option
+    pub option_text: Option<&'a str>,
+    /// Value of the element's `name`
+    pub name: Option<&'a str>,
+    /// Value of a radio button, which tells it apart within its group
+    pub radio_value: Option<&'a str>,
+    /// Value of the element's `value`
+    pub value: Option<&'a str>,
+    /// Value of an `<optgroup>`'s `label`
+    pub group_label: Option<&'a str>,
+    /// Non-empty `alt` text of an image
+    pub alt: Option<&'a str>,
+    /// Last path segment of the element's static `href`
+    pub href_segment: Option<&'a str>,
+    /// Title of the nearest ancestor carrying one, used when nothing else describes the element
+    pub ancestor_title: Option<&'a str>,
+    /// A button's `submit` or `reset` type, used when nothing else describes it
+    pub button_type: Option<&'a str>,
     pub element_index: usize,
 }
 
+/// How reliable a generated ID is as a selector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdConfidence {
+    /// Derived from an explicit label, id or `data-testid`
+    High,
+    /// Derived from static text, aria-label, placeholder, title or option text
+    Medium,
+    /// Derived from a fallback descriptor, a position or a hash
+    Low,
+}
+
+impl IdConfidence {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdConfidence::High => "high",
+            IdConfidence::Medium => "medium",
+            IdConfidence::Low => "low",
+        }
+    }
+}
+
+/// A generated ID and how reliable it is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedId {
+    pub id: String,
+    pub confidence: IdConfidence,
+    /// The normalized descriptor (inferred or fallback) that went into the ID, if any
+    pub descriptor: Option<String>,
+}
+
 /// Generate a unique ID for an element
-pub fn generate_id(config: &PluginConfig, ctx: &IdContext) -> String {
-    let mut parts: Vec<String> = vec![config.id_prefix.clone()];
+pub fn generate_id(config: &PluginConfig, ctx: &IdContext) -> GeneratedId {
+    let prefix = config.id_prefix_for(ctx.component_name);
+    let mut parts: Vec<String> = vec![prefix.to_string()];
+
+    // Content-addressed IDs ignore where the element is rendered
+    let content_addressed = config.id_strategy == IdStrategy::Content;
 
     // Add component name
-    if config.include_component_name {
+    if config.include_component_name && !content_addressed {
         if let Some(name) = ctx.component_name {
             parts.push(to_kebab_case(name));
         }
     }
 
     // Add file path (optional)
-    if config.include_file_path {
+    if config.include_file_path && !content_addressed {
         let file_part = extract_file_name(ctx.file_path);
         if !file_part.is_empty() {
             parts.push(to_kebab_case(&file_part));
         }
     }
 
-    // Add descriptive part (prefer existing id > text > aria > placeholder > title)
-    let descriptor = ctx
-        .existing_id
-        .or(ctx.text_content)
-        .or(ctx.aria_label)
-        .or(ctx.placeholder)
-        .or(ctx.title);
+    // Add descriptive part
+    // (the label, a radio's value, then the tag's sources, `id_sources` or the built-in chain)
+    let sources = config
+        .descriptor_sources_by_tag
+        .get(ctx.tag_name)
+        .or(config.id_sources.as_ref())
+        .map_or_else(
+            || default_descriptor_sources(ctx.tag_name),
+            |sources| sources.as_slice(),
+        );
+    let label = ctx.label.map(|v| (v, IdConfidence::High));
+    let (descriptor, mut confidence) = label
+        .or_else(|| ctx.radio_value.map(|v| (v, IdConfidence::Medium)))
+        .or_else(|| {
+            sources
+                .iter()
+                .find_map(|&source| descriptor_from(config, ctx, source))
+        })
+        .map_or((None, IdConfidence::Low), |(v, confidence)| {
+            (Some(v), confidence)
+        });
+    // A `data-testid` is a stable hook for the element, whatever the ID is derived from
+    if ctx.test_id.is_some() {
+        confidence = IdConfidence::High;
+    }
 
-    if let Some(desc) = descriptor {
-        let normalized = normalize_text(desc);
-        if !normalized.is_empty() {
-            parts.push(normalized);
-        }
+    let normalize = |s: &str| {
+        let split = if config.split_identifiers {
+            split_identifier(s)
+        } else {
+            Cow::Borrowed(s)
+        };
+        let s = if config.strip_call_to_action_prefixes {
+            strip_call_to_action(config, &split)
+        } else {
+            Cow::Borrowed(split.as_ref())
+        };
+        normalize_text(&s, config.id_max_words, config.truncation_strategy)
+    };
+    let mut normalized = descriptor.map(normalize).unwrap_or_default();
+    if normalized.is_empty() {
+        confidence = IdConfidence::Low;
+        // Nothing usable was inferred (e.g. a glyph-only button), fall back to the
+        // nearest ancestor title, the button type, then the per-tag default
+        let fallbacks = [
+            ctx.ancestor_title,
+            ctx.button_type,
+            config.default_descriptor(ctx.tag_name, ctx.text_content),
+        ];
+        normalized = fallbacks
+            .into_iter()
+            .flatten()
+            .map(normalize)
+            .find(|n| !n.is_empty())
+            .unwrap_or_default();
     }
+    let descriptor = (!normalized.is_empty()).then_some(normalized);
+    parts.extend(descriptor.clone());
 
     // Add element type
+    // (namespaced tags like `my:widget` become `my-widget`)
     let semantic_type = get_element_type_suffix(ctx.tag_name);
-    parts.push(semantic_type.to_string());
+    parts.push(semantic_type.replace(':', "-"));
 
-    let id = parts.join("-");
+    if content_addressed && config.content_id_include_index {
+        parts.push(config.format_index(ctx.element_index));
+        confidence = IdConfidence::Low;
+    }
 
     // Optionally hash for shorter IDs
     if config.hash_ids {
-        hash_id(&id)
+        let mut id = join_parts(config, &parts);
+        // Identical elements in different files would otherwise get the same hash
+        if config.guarantee_global_unique {
+            id = format!("{}|{}", id, file_salt(ctx.file_path));
+        }
+        let id = hash_id(prefix, &id, &config.id_separator);
+        GeneratedId {
+            id: collapse_separators(&id, &config.id_separator),
+            confidence: IdConfidence::Low,
+            descriptor,
+        }
+    } else {
+        GeneratedId {
+            id: collapse_separators(&join_parts(config, &parts), &config.id_separator),
+            confidence,
+            descriptor,
+        }
+    }
+}
+
+/// Join the parts of an ID with `id_separator`, separating the words within each part
+/// after the prefix with `word_separator`
+fn join_parts(config: &PluginConfig, parts: &[String]) -> String {
+    let Some((prefix, rest)) = parts.split_first() else {
+        return String::new();
+    };
+    std::iter::once(prefix.clone())
+        .chain(rest.iter().map(|part| words(config, part)))
+        .collect::<Vec<_>>()
+        .join(&config.id_separator)
+}
+
+/// Separate the words of a kebab-case part with `word_separator`
+fn words(config: &PluginConfig, part: &str) -> String {
+    if config.word_separator == "-" {
+        part.to_string()
+    } else {
+        part.replace('-', &config.word_separator)
+    }
+}
+
+/// Descriptor sources tried when a tag has none configured
+fn default_descriptor_sources(tag_name: &str) -> &'static [DescriptorSource] {
+    match tag_name {
+        // Value displays have no meaningful text of their own (it's the value)
+        "output" | "meter" | "progress" => VALUE_DESCRIPTOR_SOURCES,
+        "option" => OPTION_DESCRIPTOR_SOURCES,
+        "optgroup" => OPTGROUP_DESCRIPTOR_SOURCES,
+        // An image's text alternative names it better than any other attribute
+        "img" | "area" => IMAGE_DESCRIPTOR_SOURCES,
+        _ => DEFAULT_DESCRIPTOR_SOURCES,
+    }
+}
+
+const OPTION_DESCRIPTOR_SOURCES: &[DescriptorSource] = &[
+    DescriptorSource::Text,
+    DescriptorSource::Value,
+    DescriptorSource::AriaLabel,
+    DescriptorSource::Id,
+];
+
+const OPTGROUP_DESCRIPTOR_SOURCES: &[DescriptorSource] = &[
+    DescriptorSource::GroupLabel,
+    DescriptorSource::AriaLabel,
+    DescriptorSource::Id,
+];
+
+const IMAGE_DESCRIPTOR_SOURCES: &[DescriptorSource] = &[
+    DescriptorSource::Alt,
+    DescriptorSource::Id,
+    DescriptorSource::AriaLabel,
+    DescriptorSource::Title,
+];
+
+const VALUE_DESCRIPTOR_SOURCES: &[DescriptorSource] = &[
+    DescriptorSource::AriaLabel,
+    DescriptorSource::Id,
+    DescriptorSource::Name,
+];
+
+const DEFAULT_DESCRIPTOR_SOURCES: &[DescriptorSource] = &[
+    DescriptorSource::Id,
+    DescriptorSource::Text,
+    DescriptorSource::AriaLabel,
+    DescriptorSource::Placeholder,
+    DescriptorSource::Title,
+    DescriptorSource::Option,
+    DescriptorSource::Alt,
+];
+
+/// Read a descriptor source from the context, with the confidence it gives
+fn descriptor_from<'a>(
+    config: &PluginConfig,
+    ctx: &IdContext<'a>,
+    source: DescriptorSource,
+) -> Option<(&'a str, IdConfidence)> {
+    let (value, confidence) = match source {
+        DescriptorSource::Id => (
+            ctx.existing_id
+                .map(|id| strip_generated_parts(config, ctx, id))
+                .filter(|id| !id.is_empty()),
+            IdConfidence::High,
+        ),
+        DescriptorSource::TestId => (ctx.test_id, IdConfidence::High),
+        DescriptorSource::Text => (ctx.text_content, IdConfidence::Medium),
+        DescriptorSource::AriaLabel => (ctx.aria_label, IdConfidence::Medium),
+        DescriptorSource::Placeholder => (ctx.placeholder, IdConfidence::Medium),
+        DescriptorSource::Title => (ctx.title, IdConfidence::Medium),
+        DescriptorSource::Option => (ctx.option_text, IdConfidence::Medium),
+        DescriptorSource::Name => (ctx.name, IdConfidence::Medium),
+        DescriptorSource::Href => (ctx.href_segment, IdConfidence::Medium),
+        DescriptorSource::Value => (ctx.value, IdConfidence::Medium),
+        DescriptorSource::GroupLabel => (ctx.group_label, IdConfidence::Medium),
+        DescriptorSource::Alt => (ctx.alt, IdConfidence::Medium),
+    };
+    value.map(|v| (v, confidence))
+}
+
+/// Strip the parts `generate_id` adds from an ID it may have generated earlier, so
+/// regenerating from it doesn't prefix and suffix it twice
+///
+/// `ui-login-form-submit-button` on a `<button>` in `LoginForm` gives `submit`. The
+/// component and file parts are only stripped after the prefix.
+fn strip_generated_parts<'a>(config: &PluginConfig, ctx: &IdContext, id: &'a str) -> &'a str {
+    let separator = config.id_separator.as_str();
+    let strip_part = |id: &'a str, part: &str| id.strip_prefix(part)?.strip_prefix(separator);
+
+    let mut id = id;
+    if let Some(rest) = strip_part(id, config.id_prefix_for(ctx.component_name)) {
+        id = rest;
+        if config.id_strategy != IdStrategy::Content {
+            if let Some(name) = ctx.component_name.filter(|_| config.include_component_name) {
+                id = strip_part(id, &words(config, &to_kebab_case(name))).unwrap_or(id);
+            }
+            if config.include_file_path {
+                let file_part = to_kebab_case(&extract_file_name(ctx.file_path));
+                id = strip_part(id, &words(config, &file_part)).unwrap_or(id);
+            }
+        }
+    }
+
+    let suffix = words(
+        config,
+        &get_element_type_suffix(ctx.tag_name).replace(':', "-"),
+    );
+    id.strip_suffix(suffix.as_str())
+        .and_then(|rest| rest.strip_suffix(separator))
+        .filter(|rest| !rest.is_empty())
+        .or_else(|| {
+            config
+                .split_identifiers
+                .then(|| strip_identifier_suffix(id, &suffix))
+                .flatten()
+        })
+        .unwrap_or(id)
+}
+
+/// Strip the type suffix ending an identifier (`submitOrderButton`, `submit_order_button`),
+/// where `split_identifier` makes it a word of its own
+fn strip_identifier_suffix<'a>(id: &'a str, suffix: &str) -> Option<&'a str> {
+    let start = id.len().checked_sub(suffix.len())?;
+    let (rest, last) = (id.get(..start)?, id.get(start..)?);
+    if !last.eq_ignore_ascii_case(suffix) {
+        return None;
+    }
+    let camel_case_boundary = rest.ends_with(|c: char| c.is_lowercase() || c.is_numeric())
+        && last.starts_with(char::is_uppercase);
+    let rest = match rest.strip_suffix(['_', '-']) {
+        Some(rest) => rest,
+        None if camel_case_boundary => rest,
+        None => return None,
+    };
+    Some(rest).filter(|rest| !rest.is_empty())
+}
+
+/// Split a camelCase, snake_case or kebab-case identifier into words
+///
+/// `submitOrder` gives `submit Order` and `HTMLParser` gives `HTML Parser`. Text with
+/// whitespace isn't an identifier and is returned as is.
+pub fn split_identifier(s: &str) -> Cow<'_, str> {
+    if s.trim().contains(char::is_whitespace) {
+        return Cow::Borrowed(s);
+    }
+
+    let chars: Vec<char> = s.chars().collect();
+    let mut words = String::with_capacity(s.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' {
+            words.push(' ');
+            continue;
+        }
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let starts_word = prev.is_lowercase()
+                || prev.is_numeric()
+                || (prev.is_uppercase() && chars.get(i + 1).is_some_and(|n| n.is_lowercase()));
+            if starts_word {
+                words.push(' ');
+            }
+        }
+        words.push(c);
+    }
+
+    if words == s {
+        Cow::Borrowed(s)
     } else {
-        id
+        Cow::Owned(words)
     }
 }
 
+/// Get the last path segment of an `href`, ignoring its query and fragment
+///
+/// `/account/settings?tab=2` gives `settings`. Bare fragments and root links give nothing.
+pub fn href_segment(href: &str) -> Option<&str> {
+    let path = href.split(['?', '#']).next().unwrap_or_default();
+    let path = path.split_once("://").map_or(path, |(_, rest)| {
+        rest.split_once('/').map_or("", |(_, path)| path)
+    });
+    path.rsplit('/').find(|segment| !segment.is_empty())
+}
+
+/// Context for inferring an element's semantic type
+#[derive(Debug, Default)]
+pub struct TypeContext<'a> {
+    pub tag_name: &'a str,
+    pub input_type: Option<&'a str>,
+    /// The `inputmode` virtual keyboard hint of an input
+    pub input_mode: Option<&'a str>,
+    pub placeholder: Option<&'a str>,
+    pub name: Option<&'a str>,
+    pub has_href: bool,
+    pub content_editable: bool,
+    /// A button's `aria-haspopup`, only set with `detect_triggers`
+    pub aria_haspopup: Option<&'a str>,
+    /// Whether a button has `aria-controls`, only set with `detect_triggers`
+    pub has_aria_controls: bool,
+    /// A button's `submit` or `reset` type, only set with `describe_button_type`
+    pub button_type: Option<&'a str>,
+    /// Type configured for one of the element's classes in `classname_type_map`
+    pub class_type: Option<&'a str>,
+}
+
 /// Get the semantic type for an element
-pub fn get_semantic_type(
-    tag_name: &str,
-    input_type: Option<&str>,
-    placeholder: Option<&str>,
-    name: Option<&str>,
-) -> String {
+pub fn get_semantic_type(ctx: &TypeContext) -> String {
+    let &TypeContext {
+        tag_name,
+        input_type,
+        input_mode,
+        placeholder,
+        name,
+        has_href,
+        content_editable,
+        aria_haspopup,
+        has_aria_controls,
+        button_type,
+        class_type,
+    } = ctx;
+
+    // Design-system classes are the most specific signal, then what a button does
+    if let Some(class_type) = class_type {
+        return class_type.to_string();
+    }
+    if let Some(button_type) = button_type {
+        return format!("{}-button", button_type);
+    }
+
+    // Any contenteditable element behaves like a rich-text box, whatever its tag
+    if content_editable {
+        return "richtext".to_string();
+    }
+
     match tag_name {
+        // A button opening a popup is typed by the kind of popup (`"true"` means a menu)
+        "button" if matches!(aria_haspopup, Some("true" | "menu")) => "menu-trigger".to_string(),
+        "button" if aria_haspopup == Some("listbox") => "listbox-trigger".to_string(),
+        "button" if aria_haspopup == Some("dialog") => "dialog-trigger".to_string(),
+        "button" if has_aria_controls => "disclosure".to_string(),
         "button" => "button".to_string(),
-        "a" => "link".to_string(),
+        // Without an href an anchor isn't focusable or interactive, so not a link
+        "a" if has_href => "link".to_string(),
+        "a" => "text".to_string(),
         "form" => "form".to_string(),
         "select" => "dropdown".to_string(),
         "textarea" => "textarea".to_string(),
+        "output" => "output".to_string(),
+        "meter" => "meter".to_string(),
+        "progress" => "progressbar".to_string(),
+        "option" => "option".to_string(),
+        "optgroup" => "group".to_string(),
+        "img" => "image".to_string(),
         "input" => {
             // Check input type first
             if let Some(input_type) = input_type {
@@ -101,6 +488,18 @@ pub fn get_semantic_type(
                 }
             }
 
+            // Then the virtual keyboard hint mobile forms use on text inputs
+            if let Some(input_mode) = input_mode {
+                match input_mode {
+                    "numeric" | "decimal" => return "number-input".to_string(),
+                    "tel" => return "phone-input".to_string(),
+                    "email" => return "email-input".to_string(),
+                    "search" => return "search-input".to_string(),
+                    "url" => return "url-input".to_string(),
+                    _ => {}
+                }
+            }
+
             // Infer from placeholder or name
             if let Some(p) = placeholder {
                 let lower = p.to_lowercase();
@@ -118,17 +517,9 @@ pub fn get_semantic_type(
                 }
             }
 
-            if let Some(n) = name {
-                let lower = n.to_lowercase();
-                if lower.contains("email") {
-                    return "email-input".to_string();
-                }
-                if lower.contains("password") {
-                    return "password-input".to_string();
-                }
-            }
-
-            "input".to_string()
+            name.and_then(infer_type_from_name)
+                .unwrap_or("input")
+                .to_string()
         }
         _ => tag_name.to_string(),
     }
@@ -169,22 +560,69 @@ fn to_kebab_case(s: &str) -> String {
     result
 }
 
-/// Normalize text for use in an ID
-fn normalize_text(s: &str) -> String {
-    s.to_lowercase()
+/// Normalize text for use in an ID, keeping at most `max_words` words
+///
+/// The whole string is split into words at once, so however its parts were spaced
+/// (e.g. text split across JSX lines), words are counted the same.
+fn normalize_text(s: &str, max_words: usize, strategy: TruncationStrategy) -> String {
+    let lower = s
+        .to_lowercase()
         .chars()
         .map(|c| if c.is_alphanumeric() { c } else { ' ' })
-        .collect::<String>()
-        .split_whitespace()
-        .take(4) // Limit to 4 words
-        .collect::<Vec<_>>()
-        .join("-")
+        .collect::<String>();
+    let mut words: Vec<&str> = lower.split_whitespace().collect();
+
+    if words.len() > max_words {
+        match strategy {
+            TruncationStrategy::Head => words.truncate(max_words),
+            TruncationStrategy::HeadTail if max_words >= 2 => {
+                // Keep the leading words and the final word ("confirm-your-order-payment")
+                let last = words[words.len() - 1];
+                words.truncate(max_words - 1);
+                words.push(last);
+            }
+            TruncationStrategy::HeadTail => words.truncate(max_words),
+        }
+    }
+
+    words.join("-")
+}
+
+/// Strip a call-to-action prefix ("Click to Save" gives "save"), trying the longest
+/// prefixes first. Text that is only a prefix is kept whole.
+fn strip_call_to_action<'a>(config: &PluginConfig, s: &'a str) -> Cow<'a, str> {
+    fn words(s: &str) -> Vec<String> {
+        s.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    let text = words(s);
+    let mut prefixes: Vec<Vec<String>> = config
+        .call_to_action_prefixes
+        .iter()
+        .map(String::as_str)
+        .chain(CALL_TO_ACTION_PREFIXES.iter().copied())
+        .map(words)
+        .filter(|prefix| !prefix.is_empty())
+        .collect();
+    prefixes.sort_by_key(|prefix| std::cmp::Reverse(prefix.len()));
+
+    match prefixes
+        .iter()
+        .find(|prefix| text.len() > prefix.len() && text.starts_with(prefix))
+    {
+        Some(prefix) => Cow::Owned(text[prefix.len()..].join(" ")),
+        None => Cow::Borrowed(s),
+    }
 }
 
 /// Extract file name from path (without extension)
 fn extract_file_name(path: &str) -> String {
     path.split(['/', '\\'])
-        .last()
+        .next_back()
         .unwrap_or("unknown")
         .split('.')
         .next()
@@ -196,25 +634,53 @@ fn extract_file_name(path: &str) -> String {
 fn get_element_type_suffix(tag_name: &str) -> &str {
     match tag_name {
         "a" => "link",
+        "img" => "image",
         "button" => "button",
         "input" => "input",
         "select" => "dropdown",
         "textarea" => "textarea",
         "form" => "form",
+        "progress" => "progressbar",
+        "optgroup" => "group",
         _ => tag_name,
     }
 }
 
-/// Hash an ID for shorter strings
-fn hash_id(id: &str) -> String {
+/// Collapse repeated separators and trim them from the ends, however the parts were
+/// assembled (e.g. an empty part, or an `id_prefix` of `"app-"` or `""`)
+fn collapse_separators(id: &str, separator: &str) -> String {
+    if separator.is_empty() {
+        return id.to_string();
+    }
+    id.split(separator)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// 32-bit FNV-1a hash, the same on every platform and Rust version (unlike `DefaultHasher`)
+pub fn stable_hash(s: &str) -> u32 {
+    s.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Short hash of a (project-relative) file path, the same on any OS for the same path
+pub fn file_salt(path: &str) -> String {
+    format!("{:06x}", stable_hash(&path.replace('\\', "/")) & 0xff_ffff)
+}
+
+/// Hash an ID for shorter strings, keeping the configured prefix
+fn hash_id(prefix: &str, id: &str, separator: &str) -> String {
     let mut hasher = DefaultHasher::new();
     id.hash(&mut hasher);
-    format!("ui-{:08x}", hasher.finish() as u32)
+    format!("{}{}{:08x}", prefix, separator, hasher.finish() as u32)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_to_kebab_case() {
@@ -227,13 +693,209 @@ mod tests {
 
     #[test]
     fn test_normalize_text() {
-        assert_eq!(normalize_text("Sign In"), "sign-in");
-        assert_eq!(normalize_text("Submit Form Now"), "submit-form-now");
         assert_eq!(
-            normalize_text("This is a very long text that should be truncated"),
+            normalize_text("Sign In", 4, TruncationStrategy::Head),
+            "sign-in"
+        );
+        assert_eq!(
+            normalize_text("Submit Form Now", 4, TruncationStrategy::Head),
+            "submit-form-now"
+        );
+        assert_eq!(
+            normalize_text(
+                "This is a very long text that should be truncated",
+                4,
+                TruncationStrategy::Head
+            ),
             "this-is-a-very"
         );
-        assert_eq!(normalize_text("Email!@#$Address"), "email-address");
+        assert_eq!(
+            normalize_text("Email!@#$Address", 4, TruncationStrategy::Head),
+            "email-address"
+        );
+        assert_eq!(
+            normalize_text("Submit Form Now", 2, TruncationStrategy::Head),
+            "submit-form"
+        );
+        assert_eq!(
+            normalize_text(
+                "\n  Add  to\n\t   cart -- to   wishlist ",
+                4,
+                TruncationStrategy::Head
+            ),
+            "add-to-cart-to"
+        );
+    }
+
+    #[test]
+    fn test_confidence_from_test_id() {
+        let config = PluginConfig::default();
+        let ctx = IdContext {
+            tag_name: "button",
+            test_id: Some("checkout"),
+            text_content: Some("Pay now"),
+            ..Default::default()
+        };
+        let GeneratedId { id, confidence, .. } = generate_id(&config, &ctx);
+        assert_eq!(id, "ui-pay-now-button");
+        assert_eq!(confidence, IdConfidence::High);
+
+        // Describing elements by their test ID is opt-in
+        let config = PluginConfig {
+            id_sources: Some(vec![DescriptorSource::TestId, DescriptorSource::Text]),
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &ctx).id, "ui-checkout-button");
+    }
+
+    #[test]
+    fn test_confidence_from_text() {
+        let config = PluginConfig::default();
+        let ctx = IdContext {
+            tag_name: "button",
+            text_content: Some("Pay now"),
+            ..Default::default()
+        };
+        let GeneratedId { id, confidence, .. } = generate_id(&config, &ctx);
+        assert_eq!(id, "ui-pay-now-button");
+        assert_eq!(confidence, IdConfidence::Medium);
+
+        // Hashing discards the readable descriptor
+        let config = PluginConfig {
+            hash_ids: true,
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &ctx).confidence, IdConfidence::Low);
+    }
+
+    #[test]
+    fn test_confidence_from_fallback() {
+        let config = PluginConfig::default();
+        let ctx = IdContext {
+            tag_name: "button",
+            text_content: Some("×"),
+            ..Default::default()
+        };
+        let GeneratedId { id, confidence, .. } = generate_id(&config, &ctx);
+        assert_eq!(id, "ui-close-button");
+        assert_eq!(confidence, IdConfidence::Low);
+
+        let ctx = IdContext {
+            tag_name: "input",
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &ctx).confidence, IdConfidence::Low);
+    }
+
+    #[test]
+    fn test_normalize_text_head_tail() {
+        let label = "Confirm your order and complete the payment";
+        assert_eq!(
+            normalize_text(label, 4, TruncationStrategy::Head),
+            "confirm-your-order-and"
+        );
+        assert_eq!(
+            normalize_text(label, 4, TruncationStrategy::HeadTail),
+            "confirm-your-order-payment"
+        );
+        assert_eq!(
+            normalize_text(label, 2, TruncationStrategy::HeadTail),
+            "confirm-payment"
+        );
+        assert_eq!(
+            normalize_text(label, 1, TruncationStrategy::HeadTail),
+            "confirm"
+        );
+        assert_eq!(
+            normalize_text("Sign In", 4, TruncationStrategy::HeadTail),
+            "sign-in"
+        );
+    }
+
+    #[test]
+    fn test_split_identifiers_in_ids() {
+        let ctx = IdContext {
+            tag_name: "button",
+            existing_id: Some("submitOrderButton"),
+            ..Default::default()
+        };
+        let config = PluginConfig {
+            split_identifiers: true,
+            ..Default::default()
+        };
+        // The type suffix is recognized as the identifier's last word, so it isn't doubled
+        assert_eq!(generate_id(&config, &ctx).id, "ui-submit-order-button");
+        for existing_id in ["submit_order_button", "SubmitOrderButton"] {
+            let ctx = IdContext {
+                existing_id: Some(existing_id),
+                ..ctx
+            };
+            assert_eq!(generate_id(&config, &ctx).id, "ui-submit-order-button");
+        }
+        // Only whole words are the suffix
+        let ctx = IdContext {
+            existing_id: Some("submitOrderbutton"),
+            ..ctx
+        };
+        assert_eq!(
+            generate_id(&config, &ctx).id,
+            "ui-submit-orderbutton-button"
+        );
+        assert_eq!(
+            generate_id(&PluginConfig::default(), &ctx).id,
+            "ui-submitorderbutton-button"
+        );
+    }
+
+    #[test]
+    fn test_strip_call_to_action() {
+        let config = PluginConfig {
+            strip_call_to_action_prefixes: true,
+            ..Default::default()
+        };
+        let id = |text: &str| {
+            let ctx = IdContext {
+                tag_name: "button",
+                text_content: Some(text),
+                ..Default::default()
+            };
+            generate_id(&config, &ctx).id
+        };
+
+        assert_eq!(id("Click to Save"), "ui-save-button");
+        assert_eq!(id("Tap to Continue"), "ui-continue-button");
+        assert_eq!(id("Press to Start"), "ui-start-button");
+        assert_eq!(
+            id("Click here to download the report"),
+            "ui-download-the-report-button"
+        );
+        // Nothing left to strip down to, or no prefix at all
+        assert_eq!(id("Click to"), "ui-click-to-button");
+        assert_eq!(id("Clicker to Save"), "ui-clicker-to-save-button");
+        assert_eq!(id("Save and click to close"), "ui-save-and-click-to-button");
+
+        // Off by default
+        let ctx = IdContext {
+            tag_name: "button",
+            text_content: Some("Click to Save"),
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_id(&PluginConfig::default(), &ctx).id,
+            "ui-click-to-save-button"
+        );
+
+        // Extra prefixes are matched alongside the built-in ones
+        let config = PluginConfig {
+            call_to_action_prefixes: vec!["Swipe to".into()],
+            ..config
+        };
+        let ctx = IdContext {
+            tag_name: "button",
+            text_content: Some("Swipe to unlock"),
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &ctx).id, "ui-unlock-button");
     }
 
     #[test]
@@ -241,6 +903,8 @@ mod tests {
         assert_eq!(extract_file_name("/src/components/LoginForm.tsx"), "LoginForm");
         assert_eq!(extract_file_name("C:\\Users\\App\\Button.jsx"), "Button");
         assert_eq!(extract_file_name("Component.tsx"), "Component");
+        assert_eq!(extract_file_name("/src/legacy/Checkout.js"), "Checkout");
+        assert_eq!(extract_file_name("Checkout.flow.js"), "Checkout");
     }
 
     #[test]
@@ -254,7 +918,7 @@ mod tests {
             ..Default::default()
         };
 
-        let id = generate_id(&config, &ctx);
+        let id = generate_id(&config, &ctx).id;
         assert_eq!(id, "ui-login-form-sign-in-button");
     }
 
@@ -269,14 +933,519 @@ mod tests {
             ..Default::default()
         };
 
-        let id = generate_id(&config, &ctx);
+        let id = generate_id(&config, &ctx).id;
         assert_eq!(id, "ui-login-form-enter-your-email-input");
     }
 
     #[test]
-    fn test_generate_id_hashed() {
+    fn test_generate_id_label_wins() {
+        let config = PluginConfig::default();
+        let ctx = IdContext {
+            component_name: Some("Hero"),
+            tag_name: "button",
+            label: Some("Primary Action"),
+            existing_id: Some("cta"),
+            text_content: Some("Go"),
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_id(&config, &ctx).id,
+            "ui-hero-primary-action-button"
+        );
+    }
+
+    #[test]
+    fn test_generate_id_descriptor_sources_by_tag() {
+        let config = PluginConfig {
+            descriptor_sources_by_tag: HashMap::from([
+                (
+                    "input".to_string(),
+                    vec![DescriptorSource::Name, DescriptorSource::Placeholder],
+                ),
+                (
+                    "button".to_string(),
+                    vec![DescriptorSource::Text, DescriptorSource::AriaLabel],
+                ),
+                (
+                    "a".to_string(),
+                    vec![DescriptorSource::Text, DescriptorSource::Href],
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        let input = IdContext {
+            tag_name: "input",
+            existing_id: Some("field-1"),
+            placeholder: Some("Your email"),
+            name: Some("email"),
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &input).id, "ui-email-input");
+        let input = IdContext {
+            name: None,
+            ..input
+        };
+        assert_eq!(generate_id(&config, &input).id, "ui-your-email-input");
+
+        // Sources not listed for the tag are ignored
+        let button = IdContext {
+            tag_name: "button",
+            existing_id: Some("cta"),
+            aria_label: Some("Close dialog"),
+            text_content: Some("Close"),
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &button).id, "ui-close-button");
+        let button = IdContext {
+            text_content: None,
+            ..button
+        };
+        assert_eq!(generate_id(&config, &button).id, "ui-close-dialog-button");
+
+        let link = IdContext {
+            tag_name: "a",
+            href_segment: Some("settings"),
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &link).id, "ui-settings-link");
+
+        // The label still wins, and other tags keep the built-in chain
+        let labelled = IdContext {
+            label: Some("Primary"),
+            ..link
+        };
+        assert_eq!(generate_id(&config, &labelled).id, "ui-primary-link");
+        let select = IdContext {
+            tag_name: "select",
+            existing_id: Some("country"),
+            name: Some("region"),
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &select).id, "ui-country-dropdown");
+    }
+
+    #[test]
+    fn test_generate_id_radio_value() {
+        let config = PluginConfig::default();
+        let ctx = IdContext {
+            component_name: Some("Shipping"),
+            tag_name: "input",
+            name: Some("speed"),
+            aria_label: Some("Shipping speed"),
+            radio_value: Some("express"),
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &ctx).id, "ui-shipping-express-input");
+
+        let labelled = IdContext {
+            label: Some("Fastest"),
+            ..ctx
+        };
+        assert_eq!(
+            generate_id(&config, &labelled).id,
+            "ui-shipping-fastest-input"
+        );
+    }
+
+    #[test]
+    fn test_generate_id_from_generated_existing_id() {
+        let config = PluginConfig::default();
+        let ctx = |existing_id| IdContext {
+            component_name: Some("Foo"),
+            tag_name: "button",
+            existing_id: Some(existing_id),
+            ..Default::default()
+        };
+
+        // Prefix, component and type suffix aren't added twice
+        for existing_id in [
+            "ui-foo-save-button",
+            "ui-save-button",
+            "save-button",
+            "save",
+        ] {
+            assert_eq!(
+                generate_id(&config, &ctx(existing_id)).id,
+                "ui-foo-save-button"
+            );
+        }
+
+        // A descriptor that is only the type suffix is kept
+        assert_eq!(
+            generate_id(&config, &ctx("ui-foo-button")).id,
+            "ui-foo-button-button"
+        );
+
+        let ctx = IdContext {
+            file_path: "/src/Checkout.tsx",
+            tag_name: "a",
+            existing_id: Some("app-checkout-terms-link"),
+            ..Default::default()
+        };
+        let config = PluginConfig {
+            id_prefix: "app".into(),
+            include_file_path: true,
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &ctx).id, "app-checkout-terms-link");
+    }
+
+    #[test]
+    fn test_generate_id_button_type() {
+        let config = PluginConfig::default();
+        let ctx = IdContext {
+            component_name: Some("Signup"),
+            tag_name: "button",
+            button_type: Some("submit"),
+            ..Default::default()
+        };
+        let generated = generate_id(&config, &ctx);
+        assert_eq!(generated.id, "ui-signup-submit-button");
+        assert_eq!(generated.confidence, IdConfidence::Low);
+
+        // Only used when nothing else describes the button
+        let ctx = IdContext {
+            text_content: Some("Create account"),
+            ..ctx
+        };
+        assert_eq!(
+            generate_id(&config, &ctx).id,
+            "ui-signup-create-account-button"
+        );
+    }
+
+    #[test]
+    fn test_generate_id_value_display() {
+        let config = PluginConfig::default();
+        let ctx = IdContext {
+            component_name: Some("Upload"),
+            tag_name: "progress",
+            // The displayed value isn't a descriptor
+            text_content: Some("70%"),
+            name: Some("upload"),
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_id(&config, &ctx).id,
+            "ui-upload-upload-progressbar"
+        );
+
+        let ctx = IdContext {
+            tag_name: "output",
+            existing_id: Some("total"),
+            aria_label: Some("Order total"),
+            ..ctx
+        };
+        assert_eq!(
+            generate_id(&config, &ctx).id,
+            "ui-upload-order-total-output"
+        );
+    }
+
+    #[test]
+    fn test_generate_id_image_alt() {
+        let config = PluginConfig::default();
+        let ctx = IdContext {
+            component_name: Some("Header"),
+            tag_name: "img",
+            alt: Some("Company logo"),
+            existing_id: Some("brand"),
+            title: Some("Home"),
+            ..Default::default()
+        };
+        let generated = generate_id(&config, &ctx);
+        assert_eq!(generated.id, "ui-header-company-logo-image");
+        assert_eq!(generated.confidence, IdConfidence::Medium);
+
+        // Other elements only fall back to it
+        let ctx = IdContext {
+            tag_name: "input",
+            ..ctx
+        };
+        assert_eq!(generate_id(&config, &ctx).id, "ui-header-brand-input");
+    }
+
+    #[test]
+    fn test_generate_id_global_unique() {
+        let config = PluginConfig {
+            guarantee_global_unique: true,
+            ..Default::default()
+        };
+        let ctx = |file_path| IdContext {
+            component_name: Some("LoginForm"),
+            file_path,
+            tag_name: "button",
+            text_content: Some("Sign In"),
+            ..Default::default()
+        };
+
+        // Readable IDs are left alone; only their collision suffixes are salted
+        let a = generate_id(&config, &ctx("src/admin/LoginForm.tsx")).id;
+        let b = generate_id(&config, &ctx("src/shop/LoginForm.tsx")).id;
+        assert_eq!(a, "ui-login-form-sign-in-button");
+        assert_eq!(a, b);
+
+        // The salt is stable across platforms and builds, and ignores path separators
+        assert_eq!(file_salt("src/admin/LoginForm.tsx"), "d95b44");
+        assert_eq!(file_salt("src\\admin\\LoginForm.tsx"), "d95b44");
+        assert_ne!(file_salt("src/shop/LoginForm.tsx"), "d95b44");
+
+        let hashed = PluginConfig {
+            hash_ids: true,
+            ..config
+        };
+        let a = generate_id(&hashed, &ctx("src/admin/LoginForm.tsx")).id;
+        let b = generate_id(&hashed, &ctx("src/shop/LoginForm.tsx")).id;
+        assert_ne!(a, b);
+        assert_eq!(a.len(), "ui-".len() + 8);
+    }
+
+    #[test]
+    fn test_generate_id_option_and_optgroup() {
+        let config = PluginConfig::default();
+        let option = IdContext {
+            component_name: Some("Shipping"),
+            tag_name: "option",
+            text_content: Some("United Kingdom"),
+            value: Some("gb"),
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_id(&config, &option).id,
+            "ui-shipping-united-kingdom-option"
+        );
+        let option = IdContext {
+            text_content: None,
+            ..option
+        };
+        assert_eq!(generate_id(&config, &option).id, "ui-shipping-gb-option");
+
+        let group = IdContext {
+            component_name: Some("Shipping"),
+            tag_name: "optgroup",
+            group_label: Some("Europe"),
+            existing_id: Some("eu"),
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &group).id, "ui-shipping-europe-group");
+    }
+
+    #[test]
+    fn test_generate_id_collapses_separators() {
+        assert_eq!(collapse_separators("ui--button", "-"), "ui-button");
+        assert_eq!(
+            collapse_separators("-ui---save--button-", "-"),
+            "ui-save-button"
+        );
+        assert_eq!(collapse_separators("ui-save-button", "-"), "ui-save-button");
+        assert_eq!(
+            collapse_separators("ui..save.button.", "."),
+            "ui.save.button"
+        );
+
+        let ctx = IdContext {
+            component_name: Some("Toolbar"),
+            tag_name: "button",
+            // Normalizes to an empty descriptor between the component and the type
+            text_content: Some("…"),
+            ..Default::default()
+        };
+        let config = |id_prefix: &str| PluginConfig {
+            id_prefix: id_prefix.into(),
+            ..Default::default()
+        };
+
+        assert_eq!(generate_id(&config("ui"), &ctx).id, "ui-toolbar-button");
+        // A prefix carrying its own separator
+        assert_eq!(generate_id(&config("app-"), &ctx).id, "app-toolbar-button");
+        assert_eq!(generate_id(&config(""), &ctx).id, "toolbar-button");
+
+        let hashed = PluginConfig {
+            hash_ids: true,
+            ..config("app-")
+        };
+        let id = generate_id(&hashed, &ctx).id;
+        assert!(id.starts_with("app-") && !id.contains("--"), "{}", id);
+    }
+
+    #[test]
+    fn test_generate_id_separators() {
+        let config = PluginConfig {
+            word_separator: "_".into(),
+            ..Default::default()
+        };
+        let ctx = IdContext {
+            component_name: Some("LoginForm"),
+            tag_name: "button",
+            text_content: Some("Sign In"),
+            ..Default::default()
+        };
+        let GeneratedId { id, descriptor, .. } = generate_id(&config, &ctx);
+        assert_eq!(id, "ui-login_form-sign_in-button");
+        assert_eq!(descriptor.as_deref(), Some("sign-in"));
+
+        // Parts can be told apart from words
+        let config = PluginConfig {
+            id_separator: ".".into(),
+            word_separator: "_".into(),
+            ..config
+        };
+        assert_eq!(
+            generate_id(&config, &ctx).id,
+            "ui.login_form.sign_in.button"
+        );
+
+        // An ID generated with the same separators is reused as the descriptor
+        let regenerated = IdContext {
+            text_content: None,
+            existing_id: Some("ui.login_form.sign_in.button"),
+            ..ctx
+        };
+        assert_eq!(
+            generate_id(&config, &regenerated).id,
+            "ui.login_form.sign_in.button"
+        );
+
+        let hashed = PluginConfig {
+            hash_ids: true,
+            ..config
+        };
+        assert!(generate_id(&hashed, &ctx).id.starts_with("ui."));
+    }
+
+    #[test]
+    fn test_generate_id_component_prefix() {
+        let config = PluginConfig {
+            component_prefixes: HashMap::from([("CheckoutForm".into(), "checkout".into())]),
+            ..Default::default()
+        };
+        let ctx = |component_name| IdContext {
+            component_name: Some(component_name),
+            tag_name: "button",
+            text_content: Some("Pay"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            generate_id(&config, &ctx("CheckoutForm")).id,
+            "checkout-checkout-form-pay-button"
+        );
+        // Other components keep the global prefix
+        assert_eq!(
+            generate_id(&config, &ctx("LoginForm")).id,
+            "ui-login-form-pay-button"
+        );
+
+        let hashed = PluginConfig {
+            hash_ids: true,
+            ..config
+        };
+        assert!(generate_id(&hashed, &ctx("CheckoutForm"))
+            .id
+            .starts_with("checkout-"));
+    }
+
+    #[test]
+    fn test_split_identifier() {
+        assert_eq!(split_identifier("submitOrder"), "submit Order");
+        assert_eq!(split_identifier("SubmitOrderButton"), "Submit Order Button");
+        assert_eq!(split_identifier("submit_order"), "submit order");
+        assert_eq!(split_identifier("submit-order"), "submit order");
+        assert_eq!(split_identifier("parseHTMLResponse"), "parse HTML Response");
+        assert_eq!(split_identifier("step2Next"), "step2 Next");
+        assert!(matches!(
+            split_identifier("submit"),
+            Cow::Borrowed("submit")
+        ));
+        assert!(matches!(
+            split_identifier("Submit Order"),
+            Cow::Borrowed("Submit Order")
+        ));
+    }
+
+    #[test]
+    fn test_href_segment() {
+        assert_eq!(href_segment("/account/settings?tab=2"), Some("settings"));
+        assert_eq!(href_segment("/docs/"), Some("docs"));
+        assert_eq!(
+            href_segment("https://example.com/pricing#plans"),
+            Some("pricing")
+        );
+        assert_eq!(href_segment("https://example.com"), None);
+        assert_eq!(href_segment("#top"), None);
+        assert_eq!(href_segment("/"), None);
+    }
+
+    #[test]
+    fn test_generate_id_default_descriptor() {
         let mut config = PluginConfig::default();
-        config.hash_ids = true;
+        let ctx = IdContext {
+            component_name: Some("Component"),
+            tag_name: "form",
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &ctx).id, "ui-component-form");
+
+        config
+            .default_descriptors
+            .insert("form".into(), "form".into());
+        assert_eq!(generate_id(&config, &ctx).id, "ui-component-form-form");
+
+        let ctx = IdContext {
+            component_name: Some("Component"),
+            tag_name: "button",
+            text_content: Some("×"),
+            ..Default::default()
+        };
+        assert_eq!(generate_id(&config, &ctx).id, "ui-component-close-button");
+    }
+
+    #[test]
+    fn test_generate_id_content_strategy() {
+        let config = PluginConfig {
+            id_strategy: IdStrategy::Content,
+            include_file_path: true,
+            ..Default::default()
+        };
+        let in_header = IdContext {
+            component_name: Some("Header"),
+            file_path: "/src/Header.tsx",
+            tag_name: "button",
+            text_content: Some("Sign In"),
+            element_index: 1,
+            ..Default::default()
+        };
+        let in_sidebar = IdContext {
+            component_name: Some("Sidebar"),
+            file_path: "/src/layout/Sidebar.tsx",
+            tag_name: "button",
+            text_content: Some("Sign In"),
+            element_index: 3,
+            ..Default::default()
+        };
+
+        assert_eq!(generate_id(&config, &in_header).id, "ui-sign-in-button");
+        assert_eq!(
+            generate_id(&config, &in_header).id,
+            generate_id(&config, &in_sidebar).id
+        );
+
+        let config = PluginConfig {
+            content_id_include_index: true,
+            ..config
+        };
+        assert_eq!(generate_id(&config, &in_header).id, "ui-sign-in-button-1");
+        assert_eq!(generate_id(&config, &in_sidebar).id, "ui-sign-in-button-3");
+    }
+
+    #[test]
+    fn test_generate_id_hashed() {
+        let config = PluginConfig {
+            hash_ids: true,
+            id_prefix: "app".into(),
+            ..Default::default()
+        };
 
         let ctx = IdContext {
             component_name: Some("LoginForm"),
@@ -285,27 +1454,166 @@ mod tests {
             ..Default::default()
         };
 
-        let id = generate_id(&config, &ctx);
-        assert!(id.starts_with("ui-"));
-        assert_eq!(id.len(), 11); // "ui-" + 8 hex chars
+        let id = generate_id(&config, &ctx).id;
+        assert!(id.starts_with("app-"));
+        assert_eq!(id.len(), config.id_prefix.len() + 1 + 8); // "app-" + 8 hex chars
+    }
+
+    /// Type context for a `tag_name` element with nothing else set
+    fn tag(tag_name: &str) -> TypeContext<'_> {
+        TypeContext {
+            tag_name,
+            ..Default::default()
+        }
     }
 
     #[test]
     fn test_get_semantic_type() {
-        assert_eq!(get_semantic_type("button", None, None, None), "button");
-        assert_eq!(get_semantic_type("a", None, None, None), "link");
+        assert_eq!(get_semantic_type(&tag("button")), "button");
         assert_eq!(
-            get_semantic_type("input", Some("email"), None, None),
+            get_semantic_type(&TypeContext {
+                has_href: true,
+                ..tag("a")
+            }),
+            "link"
+        );
+        assert_eq!(get_semantic_type(&tag("a")), "text");
+        assert_eq!(
+            get_semantic_type(&TypeContext {
+                input_type: Some("email"),
+                ..tag("input")
+            }),
             "email-input"
         );
         assert_eq!(
-            get_semantic_type("input", Some("password"), None, None),
+            get_semantic_type(&TypeContext {
+                input_type: Some("password"),
+                ..tag("input")
+            }),
             "password-input"
         );
         assert_eq!(
-            get_semantic_type("input", None, Some("Enter email"), None),
+            get_semantic_type(&TypeContext {
+                placeholder: Some("Enter email"),
+                ..tag("input")
+            }),
             "email-input"
         );
-        assert_eq!(get_semantic_type("input", None, None, None), "input");
+        assert_eq!(get_semantic_type(&tag("input")), "input");
+        assert_eq!(
+            get_semantic_type(&TypeContext {
+                content_editable: true,
+                ..tag("div")
+            }),
+            "richtext"
+        );
+        assert_eq!(get_semantic_type(&tag("output")), "output");
+        assert_eq!(get_semantic_type(&tag("meter")), "meter");
+        assert_eq!(get_semantic_type(&tag("progress")), "progressbar");
+        assert_eq!(get_semantic_type(&tag("option")), "option");
+        assert_eq!(get_semantic_type(&tag("optgroup")), "group");
+    }
+
+    #[test]
+    fn test_semantic_type_from_name() {
+        let input = |input_type, name| {
+            get_semantic_type(&TypeContext {
+                input_type,
+                name: Some(name),
+                ..tag("input")
+            })
+        };
+        assert_eq!(input(None, "phone"), "phone-input");
+        // An explicit type wins over the name
+        assert_eq!(input(Some("password"), "username"), "password-input");
+        assert_eq!(input(Some("email"), "phone"), "email-input");
+    }
+
+    #[test]
+    fn test_get_semantic_type_triggers() {
+        let button = |aria_haspopup, has_aria_controls| {
+            get_semantic_type(&TypeContext {
+                aria_haspopup,
+                has_aria_controls,
+                ..tag("button")
+            })
+        };
+
+        assert_eq!(button(Some("menu"), false), "menu-trigger");
+        assert_eq!(button(Some("true"), false), "menu-trigger");
+        assert_eq!(button(Some("listbox"), true), "listbox-trigger");
+        assert_eq!(button(Some("dialog"), false), "dialog-trigger");
+        assert_eq!(button(Some("false"), true), "disclosure");
+        assert_eq!(button(None, true), "disclosure");
+        assert_eq!(button(Some("false"), false), "button");
+    }
+
+    #[test]
+    fn test_get_semantic_type_class_type() {
+        // A class-driven type wins over the tag and over contenteditable
+        assert_eq!(
+            get_semantic_type(&TypeContext {
+                has_href: true,
+                class_type: Some("nav-link"),
+                ..tag("a")
+            }),
+            "nav-link"
+        );
+        assert_eq!(
+            get_semantic_type(&TypeContext {
+                content_editable: true,
+                class_type: Some("editor"),
+                ..tag("div")
+            }),
+            "editor"
+        );
+        // and over a button's type
+        let submit = TypeContext {
+            button_type: Some("submit"),
+            ..tag("button")
+        };
+        assert_eq!(get_semantic_type(&submit), "submit-button");
+        assert_eq!(
+            get_semantic_type(&TypeContext {
+                class_type: Some("primary-button"),
+                ..submit
+            }),
+            "primary-button"
+        );
+    }
+
+    #[test]
+    fn test_get_semantic_type_input_mode() {
+        let input = |input_type, input_mode| {
+            get_semantic_type(&TypeContext {
+                input_type,
+                input_mode: Some(input_mode),
+                ..tag("input")
+            })
+        };
+        for (input_mode, expected) in [
+            ("numeric", "number-input"),
+            ("decimal", "number-input"),
+            ("tel", "phone-input"),
+            ("email", "email-input"),
+            ("search", "search-input"),
+            ("text", "input"),
+        ] {
+            assert_eq!(input(None, input_mode), expected);
+        }
+
+        // Specific types still win, while `text` defers to the input mode
+        assert_eq!(input(Some("email"), "numeric"), "email-input");
+        assert_eq!(input(Some("text"), "tel"), "phone-input");
+        // The input mode wins over placeholder and name hints
+        assert_eq!(
+            get_semantic_type(&TypeContext {
+                input_mode: Some("numeric"),
+                placeholder: Some("Search"),
+                name: Some("email"),
+                ..tag("input")
+            }),
+            "number-input"
+        );
     }
 }
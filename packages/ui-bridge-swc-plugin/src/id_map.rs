@@ -0,0 +1,148 @@
+//! Generated ID source locations
+//!
+//! Writes where each emitted ID comes from, for tooling that jumps from a failing selector
+//! to source:
+//!
+//! ```json
+//! {
+//!   "ui-login-form-sign-in-button": { "file": "src/LoginForm.tsx", "line": 42, "component": "LoginForm" }
+//! }
+//! ```
+//!
+//! Files can be transformed in parallel by plugin instances that share no memory, so
+//! `id_map_path` is a directory holding one such map per source file. Consumers merge the
+//! fragments (e.g. `Object.assign({}, ...fragments)`). A fragment is written to a
+//! temporary file and renamed into place, so readers never see a partial one. Writing
+//! needs the `native` feature.
+
+use crate::id_generator::stable_hash;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Where an ID was emitted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IdLocation {
+    pub file: String,
+    /// One-based line of the element's opening tag, when the host provides a source map
+    pub line: Option<usize>,
+    pub component: Option<String>,
+    /// ID of the nearest instrumented ancestor, recorded in top-down traversal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+}
+
+/// Render a file's locations as JSON keyed by ID, in sorted order
+pub fn render_id_map(locations: &BTreeMap<String, IdLocation>) -> String {
+    let mut json = serde_json::to_string_pretty(locations).unwrap_or_default();
+    json.push('\n');
+    json
+}
+
+/// Name of the fragment holding `filename`'s IDs: its file name, made unique by a stable
+/// hash of its (project-relative) path, so every machine names it the same
+pub fn fragment_name(filename: &str) -> String {
+    let path = filename.replace('\\', "/");
+    let base = path
+        .rsplit('/')
+        .next()
+        .filter(|base| !base.is_empty())
+        .unwrap_or("unknown");
+    format!("{}-{:08x}.json", base, stable_hash(&path))
+}
+
+/// Write `filename`'s locations to its fragment in `dir`. A file left without IDs still
+/// gets an (empty) fragment, replacing its previous one.
+pub fn write_id_map(dir: &str, filename: &str, locations: &BTreeMap<String, IdLocation>) {
+    write(dir, &fragment_name(filename), &render_id_map(locations));
+}
+
+#[cfg(feature = "native")]
+fn write(dir: &str, fragment: &str, json: &str) {
+    let path = std::path::Path::new(dir).join(fragment);
+    if std::fs::read_to_string(&path).is_ok_and(|existing| existing == json) {
+        return;
+    }
+
+    let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&tmp, json))
+        .and_then(|_| std::fs::rename(&tmp, &path));
+    if let Err(err) = result {
+        eprintln!(
+            "[ui-bridge-swc-plugin] Warning: can't write idMapPath \"{}\": {}",
+            path.display(),
+            err
+        );
+    }
+}
+
+/// Without the `native` feature, locations are only rendered
+#[cfg(not(feature = "native"))]
+fn write(_dir: &str, _fragment: &str, _json: &str) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(line: usize) -> IdLocation {
+        IdLocation {
+            file: "src/LoginForm.tsx".into(),
+            line: Some(line),
+            component: Some("LoginForm".into()),
+            parent: None,
+        }
+    }
+
+    #[test]
+    fn test_render_id_map() {
+        let locations = BTreeMap::from([
+            ("ui-login-form-sign-in-button".to_string(), location(42)),
+            ("ui-login-form-email-input".to_string(), location(40)),
+        ]);
+        let map: serde_json::Value = serde_json::from_str(&render_id_map(&locations)).unwrap();
+        assert_eq!(
+            map["ui-login-form-sign-in-button"],
+            serde_json::json!({ "file": "src/LoginForm.tsx", "line": 42, "component": "LoginForm" })
+        );
+
+        // Sorted by ID, so regenerated maps diff cleanly
+        let json = render_id_map(&locations);
+        let email = json.find("ui-login-form-email-input").unwrap();
+        let sign_in = json.find("ui-login-form-sign-in-button").unwrap();
+        assert!(email < sign_in);
+
+        let unknown = IdLocation {
+            line: None,
+            component: None,
+            ..location(1)
+        };
+        let json = render_id_map(&BTreeMap::from([("ui-button".to_string(), unknown)]));
+        assert!(json.contains(r#""line": null"#));
+        assert!(json.contains(r#""component": null"#));
+    }
+
+    #[test]
+    fn test_fragment_name() {
+        // The same on every platform and build
+        let name = fragment_name("src/LoginForm.tsx");
+        assert_eq!(name, "LoginForm.tsx-9e8eda4c.json");
+        assert_eq!(name, fragment_name("src\\LoginForm.tsx"));
+        assert_ne!(name, fragment_name("admin/LoginForm.tsx"));
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_write_id_map() {
+        let dir = std::env::temp_dir().join(format!("ui-id-map-{}", std::process::id()));
+        let dir = dir.to_str().unwrap();
+
+        let locations =
+            BTreeMap::from([("ui-login-form-sign-in-button".to_string(), location(42))]);
+        write_id_map(dir, "/app/src/LoginForm.tsx", &locations);
+        let path = std::path::Path::new(dir).join(fragment_name("/app/src/LoginForm.tsx"));
+        let json = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(json, render_id_map(&locations));
+    }
+}
@@ -0,0 +1,251 @@
+//! Generated ID type declarations
+//!
+//! Collects the IDs emitted across a build and writes them to `types_output_path` as a
+//! TypeScript union, so selectors like `getByUiId("ui-login-form-sign-in-button")` can be
+//! type-checked:
+//!
+//! ```ts
+//! export type UiId =
+//!   | "ui-login-form-sign-in-button"
+//!   | "ui-login-form-email-input";
+//! ```
+//!
+//! Files can be transformed in parallel by plugin instances that share no memory, so each
+//! source file's IDs are written as a fragment (a JSON array) to a directory next to the
+//! declaration, `.<file name>-ids` (e.g. `src/.ui-ids.d.ts-ids/` for `src/ui-ids.d.ts`),
+//! and the declaration is rebuilt from all the fragments. A file transformed again (e.g.
+//! in watch mode) replaces its fragment, and with it its previous IDs. Writing needs the
+//! `native` feature.
+//!
+//! Two transforms can list the fragments before either has written the declaration, and
+//! the one writing last may not have seen the other's fragment. So after writing it, each
+//! transform lists the fragments again and rebuilds the declaration until it covers them.
+
+use crate::id_map::fragment_name;
+use std::collections::BTreeSet;
+
+/// Record the IDs emitted for `filename` and rewrite the declaration at `path` if they changed
+pub fn update_id_types(path: &str, filename: &str, ids: &[String]) {
+    let fragment = render_fragment(ids);
+    if let Some(fragments) = write_fragment(path, &fragment_name(filename), &fragment) {
+        rebuild_declaration(path, fragments);
+    }
+}
+
+/// Write the declaration of `fragments`, again as long as the fragments listed after writing
+/// it add IDs (see the module docs)
+fn rebuild_declaration(path: &str, mut fragments: Vec<String>) {
+    loop {
+        let declaration = merge_fragments(fragments.iter().map(String::as_str));
+        match write_declaration(path, &declaration) {
+            Some(current) if merge_fragments(current.iter().map(String::as_str)) != declaration => {
+                fragments = current;
+            }
+            _ => return,
+        }
+    }
+}
+
+/// Render a file's IDs as a JSON array, in sorted order
+pub fn render_fragment(ids: &[String]) -> String {
+    let ids: BTreeSet<&str> = ids.iter().map(String::as_str).collect();
+    let mut json = serde_json::to_string(&ids).unwrap_or_default();
+    json.push('\n');
+    json
+}
+
+/// Render the declaration of every ID in the given fragments. Unreadable fragments
+/// (e.g. from an older version) are skipped.
+pub fn merge_fragments<'a>(fragments: impl IntoIterator<Item = &'a str>) -> String {
+    let files: Vec<Vec<String>> = fragments
+        .into_iter()
+        .filter_map(|json| serde_json::from_str(json).ok())
+        .collect();
+    let all: BTreeSet<&str> = files.iter().flatten().map(String::as_str).collect();
+    render_id_types(&all)
+}
+
+/// Render IDs as a TypeScript union type, in sorted order
+pub fn render_id_types(ids: &BTreeSet<&str>) -> String {
+    let mut out = String::from("// Generated by ui-bridge-swc-plugin. Do not edit.\n\n");
+    if ids.is_empty() {
+        out.push_str("export type UiId = never;\n");
+        return out;
+    }
+
+    out.push_str("export type UiId =");
+    for id in ids {
+        // A JSON string is a valid TypeScript string literal
+        let literal = serde_json::to_string(id).unwrap_or_default();
+        out.push_str(&format!("\n  | {}", literal));
+    }
+    out.push_str(";\n");
+    out
+}
+
+/// Directory holding the fragments of the declaration at `path`
+#[cfg(feature = "native")]
+fn fragments_dir(path: &std::path::Path) -> std::path::PathBuf {
+    let name = path
+        .file_name()
+        .map_or_else(|| "ui-ids".into(), |name| name.to_string_lossy());
+    path.with_file_name(format!(".{}-ids", name))
+}
+
+/// Write `contents` to a temporary file and rename it into place, so readers never see a
+/// partial file
+#[cfg(feature = "native")]
+fn write_atomic(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", std::process::id()));
+    std::fs::write(&tmp, contents).and_then(|_| std::fs::rename(&tmp, path))
+}
+
+/// Write `json` as the fragment named `fragment` of the declaration at `path`, returning
+/// all of its fragments, or None when the declaration is up to date or can't be written
+#[cfg(feature = "native")]
+fn write_fragment(path: &str, fragment: &str, json: &str) -> Option<Vec<String>> {
+    use std::fs;
+
+    let dir = fragments_dir(std::path::Path::new(path));
+    let fragment_path = dir.join(fragment);
+    // A file whose IDs didn't change leaves the declaration as it is
+    if std::path::Path::new(path).exists()
+        && fs::read_to_string(&fragment_path).is_ok_and(|existing| existing == json)
+    {
+        return None;
+    }
+
+    let result = fs::create_dir_all(&dir)
+        .and_then(|_| write_atomic(&fragment_path, json))
+        .and_then(|_| read_fragments(&dir));
+    result.map_err(|err| warn(path, err)).ok()
+}
+
+/// Write the declaration at `path`, returning the fragments as they are after writing it,
+/// or None when it can't be written
+#[cfg(feature = "native")]
+fn write_declaration(path: &str, declaration: &str) -> Option<Vec<String>> {
+    let file = std::path::Path::new(path);
+    let result = write_atomic(file, declaration).and_then(|_| read_fragments(&fragments_dir(file)));
+    result.map_err(|err| warn(path, err)).ok()
+}
+
+/// Read every fragment in `dir`
+#[cfg(feature = "native")]
+fn read_fragments(dir: &std::path::Path) -> std::io::Result<Vec<String>> {
+    let mut fragments = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            // Fragments replaced meanwhile are picked up by the transform replacing them
+            fragments.extend(std::fs::read_to_string(path).ok());
+        }
+    }
+    Ok(fragments)
+}
+
+#[cfg(feature = "native")]
+fn warn(path: &str, err: std::io::Error) {
+    eprintln!(
+        "[ui-bridge-swc-plugin] Warning: can't write typesOutputPath \"{}\": {}",
+        path, err
+    );
+}
+
+/// Without the `native` feature, nothing is written
+#[cfg(not(feature = "native"))]
+fn write_fragment(_path: &str, _fragment: &str, _json: &str) -> Option<Vec<String>> {
+    None
+}
+
+#[cfg(not(feature = "native"))]
+fn write_declaration(_path: &str, _declaration: &str) -> Option<Vec<String>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_render_id_types() {
+        let ts = render_id_types(&BTreeSet::from([
+            "ui-b-button",
+            "ui-a-link",
+            "ui-\"quoted\"",
+        ]));
+        assert_eq!(
+            ts,
+            r#"// Generated by ui-bridge-swc-plugin. Do not edit.
+
+export type UiId =
+  | "ui-\"quoted\""
+  | "ui-a-link"
+  | "ui-b-button";
+"#
+        );
+
+        assert!(render_id_types(&BTreeSet::new()).ends_with("export type UiId = never;\n"));
+    }
+
+    #[test]
+    fn test_merge_fragments() {
+        let login = render_fragment(&ids(&["ui-login-sign-in-button", "ui-shared-link"]));
+        assert_eq!(login, "[\"ui-login-sign-in-button\",\"ui-shared-link\"]\n");
+        let nav = render_fragment(&ids(&["ui-shared-link", "ui-nav-home-link"]));
+
+        let ts = merge_fragments([login.as_str(), nav.as_str(), "not json"]);
+        assert!(ts.contains(r#"| "ui-login-sign-in-button""#));
+        assert!(ts.contains(r#"| "ui-nav-home-link""#));
+        assert_eq!(ts.matches("ui-shared-link").count(), 1);
+
+        assert!(merge_fragments([]).ends_with("export type UiId = never;\n"));
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_update_id_types_writes_file() {
+        let dir = std::env::temp_dir().join(format!("ui-ids-{}", std::process::id()));
+        let path = dir.join("ui-ids.d.ts");
+        let path = path.to_str().unwrap();
+
+        update_id_types(path, "/app/Login.tsx", &ids(&["ui-login-sign-in-button"]));
+        update_id_types(path, "/app/Nav.tsx", &ids(&["ui-nav-home-link"]));
+        let ts = std::fs::read_to_string(path).unwrap();
+        assert!(ts.contains(r#"| "ui-login-sign-in-button""#));
+        assert!(ts.contains(r#"| "ui-nav-home-link";"#));
+
+        // A file transformed again replaces its previous IDs
+        update_id_types(path, "/app/Login.tsx", &ids(&["ui-login-log-in-button"]));
+        let ts = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(!ts.contains("ui-login-sign-in-button"));
+        assert!(ts.contains(r#"| "ui-login-log-in-button""#));
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_update_id_types_interleaved() {
+        let dir = std::env::temp_dir().join(format!("ui-ids-interleaved-{}", std::process::id()));
+        let path = dir.join("ui-ids.d.ts");
+        let path = path.to_str().unwrap();
+
+        // Login lists the fragments before Nav writes its own, then writes the declaration
+        // after Nav did
+        let login = render_fragment(&ids(&["ui-login-sign-in-button"]));
+        let fragments = write_fragment(path, &fragment_name("/app/Login.tsx"), &login).unwrap();
+        assert_eq!(fragments.len(), 1);
+        update_id_types(path, "/app/Nav.tsx", &ids(&["ui-nav-home-link"]));
+        rebuild_declaration(path, fragments);
+
+        let ts = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(ts.contains(r#"| "ui-login-sign-in-button""#));
+        assert!(ts.contains(r#"| "ui-nav-home-link";"#));
+    }
+}
@@ -42,18 +42,32 @@
 //! </button>
 //! ```
 
+use swc_core::common::comments::{Comment, CommentKind, Comments, SingleThreadedComments};
+use swc_core::common::{SourceMapper, Spanned, DUMMY_SP};
 use swc_core::ecma::ast::Program;
 use swc_core::ecma::visit::VisitMutWith;
 use swc_core::plugin::{plugin_transform, proxies::TransformPluginProgramMetadata};
 
 mod alias_generator;
-mod config;
+mod babel_compat;
+pub mod config;
 mod id_generator;
+mod id_map;
+mod id_types;
+mod name_types;
+mod roles;
+mod selectors;
+mod synonyms;
 mod text_extractor;
 mod visitor;
 
-use config::PluginConfig;
+pub use config::{options_or_env, resolve_config, ConfigWarning, PluginConfig};
+use id_map::IdLocation;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, Instant};
 use visitor::UIBridgeVisitor;
+pub use visitor::{WeakId, WeakIdReason};
 
 /// The main plugin transform entry point
 ///
@@ -61,16 +75,116 @@ use visitor::UIBridgeVisitor;
 /// It parses the plugin configuration and applies the UI Bridge transformation.
 #[plugin_transform]
 pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
-    // Parse configuration from plugin options
-    let config: PluginConfig = metadata
-        .get_transform_plugin_config()
-        .and_then(|config_str| serde_json::from_str(&config_str).ok())
-        .unwrap_or_default();
+    // Parse configuration from plugin options, or the environment when there are none
+    let (config, warnings) =
+        resolve_config(&options_or_env(metadata.get_transform_plugin_config()));
 
-    // Get filename for ID generation
+    // Get filename for ID generation, relative to the project so it's the same on every machine
     let filename = metadata
         .get_context(&swc_core::common::plugin::metadata::TransformPluginMetadataContextKind::Filename)
         .unwrap_or_else(|| "unknown".to_string());
+    let cwd = metadata
+        .get_context(&swc_core::common::plugin::metadata::TransformPluginMetadataContextKind::Cwd);
+    let filename = project_relative(&filename, cwd.as_deref());
+
+    let fail_on_weak = config.fail_on_weak;
+    let (program, weak_ids) = transform_program(
+        program,
+        config,
+        warnings,
+        filename,
+        metadata.comments,
+        Some(&metadata.source_map),
+    );
+
+    // The plugin can't fail the build, so weak IDs are only reported
+    if fail_on_weak {
+        for weak_id in weak_ids {
+            eprintln!("[ui-bridge-swc-plugin] Error: weak ID {}", weak_id);
+        }
+    }
+
+    program
+}
+
+/// `filename` relative to the project root `cwd`, with `/` separators. Paths outside the
+/// project are only normalized.
+fn project_relative(filename: &str, cwd: Option<&str>) -> String {
+    let filename = filename.replace('\\', "/");
+    let cwd = cwd.map(|cwd| cwd.replace('\\', "/"));
+    let root = cwd.as_deref().map(|cwd| cwd.trim_end_matches('/'));
+    root.filter(|root| !root.is_empty())
+        .and_then(|root| filename.strip_prefix(root)?.strip_prefix('/'))
+        .map_or_else(|| filename.clone(), str::to_string)
+}
+
+/// Weak IDs found by `transform` with `fail_on_weak`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeakIdsError {
+    pub weak_ids: Vec<WeakId>,
+}
+
+impl fmt::Display for WeakIdsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} weak ID(s):", self.weak_ids.len())?;
+        for weak_id in &self.weak_ids {
+            write!(f, "\n  {}", weak_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for WeakIdsError {}
+
+/// Apply the UI Bridge transformation to a program, for use as a library (e.g. in CI checks)
+///
+/// With `fail_on_weak`, fails with every ID that has no descriptor or needed a collision
+/// suffix, so poorly labelled elements can be caught before merge.
+pub fn transform(
+    program: Program,
+    config: PluginConfig,
+    filename: &str,
+) -> Result<Program, WeakIdsError> {
+    let fail_on_weak = config.fail_on_weak;
+    let warnings = config.validate();
+    let (program, weak_ids) = transform_program(
+        program,
+        config,
+        warnings,
+        filename.to_string(),
+        None::<SingleThreadedComments>,
+        None,
+    );
+
+    if fail_on_weak && !weak_ids.is_empty() {
+        return Err(WeakIdsError { weak_ids });
+    }
+    Ok(program)
+}
+
+/// Apply the UI Bridge transformation to a program
+///
+/// Any JSX the host parsed is instrumented, whatever the file's extension (e.g. Flow-typed
+/// `.js` files); `filename` only feeds ID generation.
+///
+/// `comments` receives the diagnostics comment when `warnings_as_comments` is on, and
+/// `source_map` resolves the lines written to `id_map_path`.
+fn transform_program<C: Comments>(
+    program: Program,
+    config: PluginConfig,
+    warnings: Vec<ConfigWarning>,
+    filename: String,
+    comments: Option<C>,
+    source_map: Option<&dyn SourceMapper>,
+) -> (Program, Vec<WeakId>) {
+    // Leave the program untouched, without constructing the visitor
+    if !config.enabled {
+        return (program, vec![]);
+    }
+
+    for warning in &warnings {
+        eprintln!("[ui-bridge-swc-plugin] Warning: {}", warning);
+    }
 
     if config.verbose {
         eprintln!("[ui-bridge-swc-plugin] Processing: {}", filename);
@@ -79,14 +193,494 @@ pub fn process_transform(program: Program, metadata: TransformPluginProgramMetad
     // Create visitor and transform the program
     let mut visitor = UIBridgeVisitor::new(config.clone(), filename.clone());
     let mut result = program;
+    let start = config.profile.then(Instant::now);
     result.visit_mut_with(&mut visitor);
+    if let Some(start) = start {
+        eprintln!(
+            "{}",
+            profile_message(&filename, start.elapsed(), config.profile_threshold_ms)
+        );
+    }
 
     if config.verbose {
+        for diagnostic in visitor.diagnostics() {
+            eprintln!("[ui-bridge-swc-plugin] {}", diagnostic);
+        }
         eprintln!("[ui-bridge-swc-plugin] Finished: {}", filename);
     }
 
-    result
+    // Instrumenting the output again should be a no-op
+    #[cfg(debug_assertions)]
+    if config.self_check {
+        let changed = visitor::self_check(&config, &filename, &result);
+        assert!(
+            changed.is_empty(),
+            "[ui-bridge-swc-plugin] self-check failed for {}: instrumenting again changed {}",
+            filename,
+            changed.join(", ")
+        );
+    }
+
+    // A safety net behind the collision handling, which skipped elements also bypass
+    let duplicates_warning = config
+        .validate_uniqueness
+        .then(|| visitor::duplicate_ids(&config, &result))
+        .filter(|duplicates| !duplicates.is_empty())
+        .map(|duplicates| format!("duplicate IDs: {}", duplicates.join(", ")));
+    if let Some(warning) = &duplicates_warning {
+        eprintln!(
+            "[ui-bridge-swc-plugin] Warning: {} in {}",
+            warning, filename
+        );
+    }
+
+    if let Some(path) = &config.types_output_path {
+        id_types::update_id_types(path, &filename, visitor.emitted_ids());
+    }
+
+    if let Some(dir) = &config.id_map_path {
+        let locations = id_map_locations(&visitor, &filename, source_map);
+        id_map::write_id_map(dir, &filename, &locations);
+    }
+
+    if config.warnings_as_comments {
+        let diagnostics: Vec<String> = warnings
+            .iter()
+            .map(|warning| format!("Warning: {}", warning))
+            .chain(visitor.diagnostics().iter().cloned())
+            .chain(duplicates_warning.map(|warning| format!("Warning: {}", warning)))
+            .collect();
+        if let Some(comments) = comments.filter(|_| !diagnostics.is_empty()) {
+            add_diagnostics_comment(&result, &comments, &diagnostics);
+        }
+    }
+
+    (result, visitor.weak_ids().to_vec())
+}
+
+/// Report a file's transform time, flagging files slower than `threshold_ms`
+fn profile_message(filename: &str, elapsed: Duration, threshold_ms: u64) -> String {
+    let ms = elapsed.as_secs_f64() * 1000.0;
+    if elapsed > Duration::from_millis(threshold_ms) {
+        format!(
+            "[ui-bridge-swc-plugin] SLOW: {} took {:.2}ms (threshold {}ms)",
+            filename, ms, threshold_ms
+        )
+    } else {
+        format!("[ui-bridge-swc-plugin] {} took {:.2}ms", filename, ms)
+    }
+}
+
+/// Locations of the IDs the visitor emitted, keyed by ID
+fn id_map_locations(
+    visitor: &UIBridgeVisitor,
+    filename: &str,
+    source_map: Option<&dyn SourceMapper>,
+) -> BTreeMap<String, IdLocation> {
+    visitor
+        .id_sites()
+        .iter()
+        .map(|site| {
+            // Elements created by earlier transforms may have no real position
+            let line = source_map
+                .filter(|_| !site.pos.is_dummy())
+                .map(|source_map| source_map.lookup_char_pos(site.pos).line);
+            let location = IdLocation {
+                file: filename.to_string(),
+                line,
+                component: site.component.clone(),
+                parent: site.parent.clone(),
+            };
+            (site.id.clone(), location)
+        })
+        .collect()
+}
+
+/// Prepend diagnostics to a program as a block comment, ahead of any directives or imports
+fn add_diagnostics_comment(program: &Program, comments: &impl Comments, diagnostics: &[String]) {
+    let mut text = String::from("*\n * [ui-bridge-swc-plugin]\n");
+    for diagnostic in diagnostics {
+        // Keep the comment from being closed early
+        text.push_str(&format!(" * {}\n", diagnostic.replace("*/", "* /")));
+    }
+    text.push(' ');
+
+    // Attach to the first statement so the comment leads the emitted file
+    let first = match program {
+        Program::Module(module) => module.body.first().map(|item| item.span()),
+        Program::Script(script) => script.body.first().map(|stmt| stmt.span()),
+    };
+    let pos = first.unwrap_or_else(|| program.span()).lo;
+
+    comments.add_leading(
+        pos,
+        Comment {
+            kind: CommentKind::Block,
+            span: DUMMY_SP,
+            text: text.into(),
+        },
+    );
 }
 
 // Note: PluginConfig and UIBridgeVisitor are already accessible via the use statements above.
 // Individual module tests are in their respective files (config.rs, visitor.rs, etc.)
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_core::common::{sync::Lrc, FileName, SourceMap};
+    use swc_core::ecma::ast::EsVersion;
+    use swc_core::ecma::codegen::{to_code, to_code_with_comments};
+    use swc_core::ecma::parser::{parse_file_as_program, EsSyntax, Syntax};
+
+    fn parse(src: &str) -> Program {
+        parse_in(&Default::default(), src)
+    }
+
+    fn parse_in(cm: &Lrc<SourceMap>, src: &str) -> Program {
+        let fm = cm.new_source_file(FileName::Anon.into(), src.to_string());
+        parse_file_as_program(
+            &fm,
+            Syntax::Es(EsSyntax {
+                jsx: true,
+                ..Default::default()
+            }),
+            EsVersion::latest(),
+            None,
+            &mut vec![],
+        )
+        .expect("failed to parse test input")
+    }
+
+    fn run(program: Program, config: PluginConfig) -> Program {
+        let (program, _) = transform_program(
+            program,
+            config,
+            vec![],
+            "Login.tsx".into(),
+            None::<SingleThreadedComments>,
+            None,
+        );
+        program
+    }
+
+    #[test]
+    fn test_validate_uniqueness() {
+        let src = r#"function Editor() {
+            return <div>
+                <button data-ui-id="ui-editor-save-button">Save</button>
+                <button data-ui-id="ui-editor-save-button">Save</button>
+            </div>;
+        }"#;
+        let config = PluginConfig {
+            validate_uniqueness: true,
+            warnings_as_comments: true,
+            ..Default::default()
+        };
+
+        let comments = SingleThreadedComments::default();
+        let (out, _) = transform_program(
+            parse(src),
+            config,
+            vec![],
+            "Editor.tsx".into(),
+            Some(&comments),
+            None,
+        );
+        let code = to_code_with_comments(Some(&comments), &out);
+        assert!(
+            code.contains(r#" * Warning: duplicate IDs: "ui-editor-save-button" (2 times)"#),
+            "{}",
+            code
+        );
+    }
+
+    #[test]
+    fn test_warnings_as_comments() {
+        let src = r#""use client";
+import { save } from "./api";
+function Editor({ items }) { return <button onClick={save}>{...items}</button>; }"#;
+        let (config, warnings) =
+            resolve_config(r#"{ "warningsAsComments": true, "typeAttribute": "uiType" }"#);
+
+        let comments = SingleThreadedComments::default();
+        let (out, _) = transform_program(
+            parse(src),
+            config,
+            warnings,
+            "Editor.tsx".into(),
+            Some(&comments),
+            None,
+        );
+        let code = to_code_with_comments(Some(&comments), &out);
+
+        assert!(
+            code.starts_with("/**\n * [ui-bridge-swc-plugin]\n"),
+            "{}",
+            code
+        );
+        assert!(code.contains(
+            r#" * Warning: typeAttribute "uiType" should be a lowercase attribute name"#
+        ));
+        assert!(code.contains(
+            r#" * <button> "ui-editor-button" is text-unresolvable: a spread child ({...}) prevents text extraction"#
+        ));
+        // The comment leads the file, ahead of the directive
+        assert!(code.find("*/").unwrap() < code.find("\"use client\"").unwrap());
+
+        // Nothing is added without the option
+        let comments = SingleThreadedComments::default();
+        let config = PluginConfig::default();
+        let (out, _) = transform_program(
+            parse(src),
+            config,
+            vec![],
+            "Editor.tsx".into(),
+            Some(&comments),
+            None,
+        );
+        assert!(!to_code_with_comments(Some(&comments), &out).contains("ui-bridge-swc-plugin"));
+    }
+
+    #[test]
+    fn test_jsx_in_js_file() {
+        // A legacy Flow file, after the host has stripped its types
+        let src = r#"// @flow
+function PayButton({ total }) { return <button onClick={pay}>Pay now</button>; }"#;
+        let config = PluginConfig {
+            include_file_path: true,
+            ..Default::default()
+        };
+
+        let (out, _) = transform_program(
+            parse(src),
+            config,
+            vec![],
+            "src/legacy/Checkout.js".into(),
+            None::<SingleThreadedComments>,
+            None,
+        );
+        assert!(to_code(&out).contains(r#"data-ui-id="ui-pay-button-checkout-pay-now-button""#));
+    }
+
+    #[test]
+    fn test_fail_on_weak() {
+        let src = r#"function Toolbar() {
+            return <>
+                <button onClick={undo}><svg /></button>
+                <button>Save</button>
+                <button>Save</button>
+                <button>Publish</button>
+            </>;
+        }"#;
+        let config = PluginConfig {
+            fail_on_weak: true,
+            ..Default::default()
+        };
+
+        let err = transform(parse(src), config.clone(), "Toolbar.tsx").unwrap_err();
+        assert_eq!(
+            err.weak_ids,
+            [
+                WeakId {
+                    id: "ui-toolbar-button".into(),
+                    reason: WeakIdReason::NoDescriptor,
+                },
+                WeakId {
+                    id: "ui-toolbar-save-button-3".into(),
+                    reason: WeakIdReason::Collision,
+                },
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "2 weak ID(s):\n  \"ui-toolbar-button\" has no descriptor\n  \"ui-toolbar-save-button-3\" needed a collision suffix"
+        );
+
+        // Well-labelled elements pass
+        let labelled = r#"function Toolbar() {
+            return <><button>Save</button><button aria-label="Undo"><svg /></button></>;
+        }"#;
+        let out = transform(parse(labelled), config, "Toolbar.tsx").unwrap();
+        assert!(to_code(&out).contains(r#"data-ui-id="ui-toolbar-undo-button""#));
+
+        // Without the mode, weak IDs aren't an error
+        assert!(transform(parse(src), PluginConfig::default(), "Toolbar.tsx").is_ok());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_self_check() {
+        let src = "function Login() { return <form><button>Sign In</button></form>; }";
+        let config = PluginConfig {
+            self_check: true,
+            ..Default::default()
+        };
+        assert!(to_code(&run(parse(src), config)).contains("data-ui-id"));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_self_check_ignored_attributes() {
+        let src = r#"function Login() {
+            return <form>
+                <button data-ui-ignore="id">Sign In</button>
+                <button data-ui-ignore="type,aliases">Cancel</button>
+                <button data-ui-ignore>Help</button>
+            </form>;
+        }"#;
+        let config = || PluginConfig {
+            self_check: true,
+            ..Default::default()
+        };
+
+        // Instrumenting the output again leaves partially ignored elements alone
+        let code = to_code(&run(parse(src), config()));
+        assert!(code.contains(r#"<button data-ui-ignore="id" data-ui-type="button""#));
+        assert_eq!(to_code(&run(parse(&code), config())), code);
+    }
+
+    #[test]
+    fn test_profile() {
+        let src = "function Login() { return <form><button>Sign In</button></form>; }";
+        let config = PluginConfig {
+            profile: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            to_code(&run(parse(src), config)),
+            to_code(&run(parse(src), PluginConfig::default()))
+        );
+
+        assert_eq!(
+            profile_message("Login.tsx", Duration::from_micros(1500), 50),
+            "[ui-bridge-swc-plugin] Login.tsx took 1.50ms"
+        );
+        assert_eq!(
+            profile_message("Login.tsx", Duration::from_millis(120), 50),
+            "[ui-bridge-swc-plugin] SLOW: Login.tsx took 120.00ms (threshold 50ms)"
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = r#"instrumenting again changed <form> "ui-login-sign-in-form""#)]
+    fn test_self_check_detects_non_idempotent_output() {
+        // Not skipping existing IDs re-instruments every element
+        let src = "function Login() { return <form><button>Sign In</button></form>; }";
+        let config = PluginConfig {
+            self_check: true,
+            skip_existing: false,
+            ..Default::default()
+        };
+        run(parse(src), config);
+    }
+
+    #[test]
+    fn test_namespaced_attribute_names() {
+        let src = r#"function Login() {
+            return <form>
+                <button>Sign In</button>
+                <button ui:id="checkout">Pay</button>
+            </form>;
+        }"#;
+        let config = || PluginConfig {
+            id_attribute: "ui:id".into(),
+            type_attribute: "ui:type".into(),
+            aliases_attribute: "ui:aliases".into(),
+            self_check: true,
+            ..Default::default()
+        };
+
+        let code = to_code(&run(parse(src), config()));
+        assert!(code
+            .contains(r#"<button ui:id="ui-login-sign-in-button" ui:type="button" ui:aliases="#));
+        assert!(!code.contains("data-ui-id"));
+        // An element that already has an ID is skipped
+        assert!(code.contains(r#"<button ui:id="checkout">"#));
+        assert_eq!(code.matches("ui:type=").count(), 2);
+
+        // Instrumenting the output again changes nothing
+        assert_eq!(to_code(&run(parse(&code), config())), code);
+
+        // A replaced ID is removed, and still describes the element
+        let config = PluginConfig {
+            skip_existing: false,
+            self_check: false,
+            ..config()
+        };
+        let code = to_code(&run(parse(src), config));
+        assert!(code.contains(r#"ui:id="ui-login-checkout-button""#));
+        assert!(!code.contains(r#""checkout""#));
+    }
+
+    #[test]
+    fn test_disabled_leaves_program_unchanged() {
+        let src = "function Login() { return <button>Sign In</button>; }";
+
+        let out = run(parse(src), PluginConfig::default());
+        assert!(to_code(&out).contains("data-ui-id"));
+
+        let config = PluginConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        let out = run(parse(src), config);
+        assert_eq!(to_code(&out), to_code(&parse(src)));
+        assert!(!to_code(&out).contains("data-ui-"));
+    }
+
+    #[test]
+    fn test_project_relative() {
+        assert_eq!(
+            project_relative("/home/ci/app/src/LoginForm.tsx", Some("/home/ci/app")),
+            "src/LoginForm.tsx"
+        );
+        assert_eq!(
+            project_relative("C:\\app\\src\\LoginForm.tsx", Some("C:\\app\\")),
+            "src/LoginForm.tsx"
+        );
+        // Paths outside the project, or sharing only a prefix with it, stay whole
+        assert_eq!(
+            project_relative("/home/ci/app2/Nav.tsx", Some("/home/ci/app")),
+            "/home/ci/app2/Nav.tsx"
+        );
+        assert_eq!(project_relative("src/Nav.tsx", None), "src/Nav.tsx");
+    }
+
+    #[test]
+    fn test_id_map_locations() {
+        let src = r#"function LoginForm() {
+    return <form>
+        <button>Sign In</button>
+    </form>;
+}"#;
+        let cm: Lrc<SourceMap> = Default::default();
+        let config = PluginConfig {
+            id_map_path: Some("/tmp/ui-id-map".into()),
+            ..Default::default()
+        };
+        let mut visitor = UIBridgeVisitor::new(config, "src/LoginForm.tsx".into());
+        parse_in(&cm, src).visit_mut_with(&mut visitor);
+
+        let locations = id_map_locations(&visitor, "src/LoginForm.tsx", Some(&*cm));
+        assert_eq!(
+            locations.keys().collect::<Vec<_>>(),
+            ["ui-login-form-sign-in-button", "ui-login-form-sign-in-form"]
+        );
+        assert_eq!(
+            locations["ui-login-form-sign-in-button"],
+            IdLocation {
+                file: "src/LoginForm.tsx".into(),
+                line: Some(3),
+                component: Some("LoginForm".into()),
+                parent: None,
+            }
+        );
+        assert_eq!(locations["ui-login-form-sign-in-form"].line, Some(2));
+
+        // Without a source map, lines are unknown
+        let locations = id_map_locations(&visitor, "src/LoginForm.tsx", None);
+        assert_eq!(locations["ui-login-form-sign-in-form"].line, None);
+    }
+}
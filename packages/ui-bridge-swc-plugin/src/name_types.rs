@@ -0,0 +1,81 @@
+//! Name-based type module
+//!
+//! Infers the semantic type of an `<input>` from its `name` when it has no explicit `type`.
+
+use crate::id_generator::split_identifier;
+
+/// Input types inferred from common field names, checked in order
+const NAME_TYPES: &[(&[&str], &str)] = &[
+    (&["email", "mail"], "email-input"),
+    (&["password", "passwd", "pwd"], "password-input"),
+    (&["search", "query", "q"], "search-input"),
+    (&["phone", "mobile", "tel", "telephone"], "phone-input"),
+    (&["url", "website", "homepage"], "url-input"),
+    (&["birthday", "birthdate", "dob", "date"], "date-input"),
+    (
+        &["zip", "zipcode", "postcode", "postalcode"],
+        "postal-code-input",
+    ),
+    (&["address", "street", "city"], "address-input"),
+    (&["username", "login", "user"], "text-input"),
+    (&["name", "firstname", "lastname", "fullname"], "text-input"),
+];
+
+/// Infer an input's semantic type from its `name` (e.g. `firstName`, `user_phone`)
+///
+/// The name is split into words (see `split_identifier`), and a keyword must match a
+/// whole word or two adjacent words run together (`postalCode` matches `postalcode`),
+/// so that e.g. `city` doesn't match `capacity`.
+pub fn infer_type_from_name(name: &str) -> Option<&'static str> {
+    let words: Vec<String> = split_identifier(name)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let pairs = words.windows(2).map(|pair| pair.concat());
+    let candidates: Vec<String> = words.iter().cloned().chain(pairs).collect();
+
+    NAME_TYPES.iter().find_map(|(keywords, input_type)| {
+        keywords
+            .iter()
+            .any(|k| candidates.iter().any(|c| c == k))
+            .then_some(*input_type)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_type_from_name() {
+        assert_eq!(infer_type_from_name("username"), Some("text-input"));
+        assert_eq!(infer_type_from_name("firstName"), Some("text-input"));
+        assert_eq!(infer_type_from_name("user_email"), Some("email-input"));
+        assert_eq!(infer_type_from_name("phone"), Some("phone-input"));
+        assert_eq!(infer_type_from_name("tel"), Some("phone-input"));
+        assert_eq!(infer_type_from_name("search"), Some("search-input"));
+        assert_eq!(infer_type_from_name("q"), Some("search-input"));
+        assert_eq!(
+            infer_type_from_name("shippingAddress"),
+            Some("address-input")
+        );
+        assert_eq!(infer_type_from_name("zip"), Some("postal-code-input"));
+        assert_eq!(
+            infer_type_from_name("postalCode"),
+            Some("postal-code-input")
+        );
+        assert_eq!(infer_type_from_name("home-page"), Some("url-input"));
+        assert_eq!(infer_type_from_name("color"), None);
+    }
+
+    #[test]
+    fn test_infer_type_from_name_whole_words() {
+        // Keywords inside other words don't match
+        assert_eq!(infer_type_from_name("hotel"), None);
+        assert_eq!(infer_type_from_name("capacity"), None);
+        assert_eq!(infer_type_from_name("updatedAt"), None);
+        assert_eq!(infer_type_from_name("contactEmail"), Some("email-input"));
+        assert_eq!(infer_type_from_name("startDate"), Some("date-input"));
+    }
+}
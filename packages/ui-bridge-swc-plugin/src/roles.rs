@@ -0,0 +1,252 @@
+//! ARIA role module
+//!
+//! Computes the ARIA role of elements, from an explicit `role` or the tag's implicit role.
+
+/// The attributes of an element that its implicit role depends on
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AttrView<'a> {
+    /// The `type` of an `<input>`
+    pub input_type: Option<&'a str>,
+    pub has_href: bool,
+    /// Whether an `<input>` is tied to a `<datalist>` via `list`
+    pub has_list: bool,
+    /// Whether a `<select>` has `multiple`
+    pub multiple: bool,
+    /// The `size` of a `<select>`
+    pub size: Option<u32>,
+    /// The `alt` of an `<img>`
+    pub alt: Option<&'a str>,
+    /// Whether the element has an `aria-label`, `aria-labelledby` or `title`
+    pub has_accessible_name: bool,
+}
+
+/// Get the implicit ARIA role of an element
+///
+/// Follows the HTML-ARIA mappings for the tags below. Roles that depend on where the
+/// element sits (e.g. a `<header>` inside an `<article>`) use the top-level mapping.
+pub fn implicit_role(tag_name: &str, attrs: &AttrView) -> Option<&'static str> {
+    let role = match tag_name {
+        "a" | "area" if attrs.has_href => "link",
+        "article" => "article",
+        "aside" => "complementary",
+        "button" => "button",
+        "datalist" => "listbox",
+        "dd" => "definition",
+        "details" | "fieldset" | "optgroup" => "group",
+        "dialog" => "dialog",
+        "dt" => "term",
+        "figure" => "figure",
+        "footer" => "contentinfo",
+        "form" => "form",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "header" => "banner",
+        "hr" => "separator",
+        // An empty `alt` marks the image as decorative
+        "img" if attrs.alt == Some("") => "presentation",
+        "img" => "img",
+        "li" => "listitem",
+        "main" => "main",
+        "menu" | "ol" | "ul" => "list",
+        "meter" => "meter",
+        "nav" => "navigation",
+        "option" => "option",
+        "output" => "status",
+        "progress" => "progressbar",
+        "search" => "search",
+        // A section is only a landmark once it's named
+        "section" if attrs.has_accessible_name => "region",
+        "select" if attrs.multiple || attrs.size.is_some_and(|size| size > 1) => "listbox",
+        "select" => "combobox",
+        "table" => "table",
+        "tbody" | "tfoot" | "thead" => "rowgroup",
+        "td" => "cell",
+        "textarea" => "textbox",
+        "th" => "columnheader",
+        "tr" => "row",
+        "input" => match attrs.input_type.unwrap_or("text") {
+            "button" | "image" | "reset" | "submit" => "button",
+            "checkbox" => "checkbox",
+            "radio" => "radio",
+            "range" => "slider",
+            "number" => "spinbutton",
+            // Text inputs suggesting values from a <datalist> are comboboxes
+            "email" | "search" | "tel" | "text" | "url" if attrs.has_list => "combobox",
+            "search" => "searchbox",
+            "email" | "tel" | "text" | "url" => "textbox",
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(role)
+}
+
+/// Get the ARIA role of an element, preferring an explicit `role` attribute
+pub fn get_role(tag_name: &str, explicit_role: Option<&str>, attrs: &AttrView) -> Option<String> {
+    explicit_role
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(String::from)
+        .or_else(|| implicit_role(tag_name, attrs).map(String::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(tag_name: &str) -> Option<&'static str> {
+        implicit_role(tag_name, &AttrView::default())
+    }
+
+    fn input_role(input_type: &str, has_list: bool) -> Option<&'static str> {
+        let attrs = AttrView {
+            input_type: Some(input_type),
+            has_list,
+            ..Default::default()
+        };
+        implicit_role("input", &attrs)
+    }
+
+    #[test]
+    fn test_implicit_role() {
+        for (tag_name, expected) in [
+            ("article", "article"),
+            ("aside", "complementary"),
+            ("button", "button"),
+            ("datalist", "listbox"),
+            ("dd", "definition"),
+            ("details", "group"),
+            ("dialog", "dialog"),
+            ("dt", "term"),
+            ("fieldset", "group"),
+            ("figure", "figure"),
+            ("footer", "contentinfo"),
+            ("form", "form"),
+            ("h1", "heading"),
+            ("h6", "heading"),
+            ("header", "banner"),
+            ("hr", "separator"),
+            ("img", "img"),
+            ("li", "listitem"),
+            ("main", "main"),
+            ("menu", "list"),
+            ("meter", "meter"),
+            ("nav", "navigation"),
+            ("ol", "list"),
+            ("optgroup", "group"),
+            ("option", "option"),
+            ("output", "status"),
+            ("progress", "progressbar"),
+            ("search", "search"),
+            ("select", "combobox"),
+            ("table", "table"),
+            ("tbody", "rowgroup"),
+            ("td", "cell"),
+            ("textarea", "textbox"),
+            ("tfoot", "rowgroup"),
+            ("th", "columnheader"),
+            ("thead", "rowgroup"),
+            ("tr", "row"),
+            ("ul", "list"),
+        ] {
+            assert_eq!(role(tag_name), Some(expected), "<{}>", tag_name);
+        }
+
+        // No implicit role
+        for tag_name in ["div", "span", "p", "label", "a", "area", "section", "svg"] {
+            assert_eq!(role(tag_name), None, "<{}>", tag_name);
+        }
+    }
+
+    #[test]
+    fn test_implicit_role_depends_on_attributes() {
+        let with_href = AttrView {
+            has_href: true,
+            ..Default::default()
+        };
+        assert_eq!(implicit_role("a", &with_href), Some("link"));
+        assert_eq!(implicit_role("area", &with_href), Some("link"));
+
+        let decorative = AttrView {
+            alt: Some(""),
+            ..Default::default()
+        };
+        assert_eq!(implicit_role("img", &decorative), Some("presentation"));
+        let described = AttrView {
+            alt: Some("Logo"),
+            ..Default::default()
+        };
+        assert_eq!(implicit_role("img", &described), Some("img"));
+
+        let named = AttrView {
+            has_accessible_name: true,
+            ..Default::default()
+        };
+        assert_eq!(implicit_role("section", &named), Some("region"));
+
+        let multiple = AttrView {
+            multiple: true,
+            ..Default::default()
+        };
+        assert_eq!(implicit_role("select", &multiple), Some("listbox"));
+        for (size, expected) in [(1, "combobox"), (4, "listbox")] {
+            let attrs = AttrView {
+                size: Some(size),
+                ..Default::default()
+            };
+            assert_eq!(implicit_role("select", &attrs), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_implicit_input_role() {
+        assert_eq!(
+            implicit_role("input", &AttrView::default()),
+            Some("textbox")
+        );
+        for (input_type, expected) in [
+            ("button", Some("button")),
+            ("image", Some("button")),
+            ("reset", Some("button")),
+            ("submit", Some("button")),
+            ("checkbox", Some("checkbox")),
+            ("radio", Some("radio")),
+            ("range", Some("slider")),
+            ("number", Some("spinbutton")),
+            ("search", Some("searchbox")),
+            ("email", Some("textbox")),
+            ("tel", Some("textbox")),
+            ("text", Some("textbox")),
+            ("url", Some("textbox")),
+            ("color", None),
+            ("date", None),
+            ("file", None),
+            ("hidden", None),
+            ("password", None),
+        ] {
+            assert_eq!(input_role(input_type, false), expected, "{}", input_type);
+        }
+
+        // With a <datalist>
+        for input_type in ["email", "search", "tel", "text", "url"] {
+            assert_eq!(
+                input_role(input_type, true),
+                Some("combobox"),
+                "{}",
+                input_type
+            );
+        }
+        assert_eq!(input_role("number", true), Some("spinbutton"));
+    }
+
+    #[test]
+    fn test_get_role() {
+        let attrs = AttrView::default();
+        assert_eq!(get_role("div", Some("tab"), &attrs), Some("tab".into()));
+        assert_eq!(
+            get_role("button", Some("menuitem"), &attrs),
+            Some("menuitem".into())
+        );
+        assert_eq!(get_role("button", Some(" "), &attrs), Some("button".into()));
+        assert_eq!(get_role("a", None, &attrs), None);
+    }
+}
@@ -0,0 +1,217 @@
+//! Selector matching module
+//!
+//! Matches elements against simple CSS-like selectors (`button.btn-primary`,
+//! `input[type=email]`, `[data-cta]`) using their static attributes at build time.
+
+use crate::text_extractor::{get_attribute_value, has_attribute};
+use swc_core::ecma::ast::JSXOpeningElement;
+
+/// A compound selector: an optional tag, classes and attribute conditions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    tag: Option<String>,
+    classes: Vec<String>,
+    /// Attribute names, with the exact value they must have (any value when None)
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl Selector {
+    /// Parse a selector, returning None when it isn't one of the supported forms
+    ///
+    /// Ids, combinators, pseudo-classes and attribute operators other than `=` aren't
+    /// supported. A `:` belongs to the name (`my:widget`), as in JSX.
+    pub fn parse(selector: &str) -> Option<Self> {
+        let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | ':');
+
+        let selector = selector.trim();
+        let tag_end = selector
+            .find(|c: char| !is_name_char(c) && c != '*')
+            .unwrap_or(selector.len());
+        let tag = match &selector[..tag_end] {
+            "" | "*" => None,
+            tag if tag.contains('*') => return None,
+            tag => Some(tag.to_string()),
+        };
+
+        let mut parsed = Selector {
+            tag,
+            classes: vec![],
+            attributes: vec![],
+        };
+        let mut rest = &selector[tag_end..];
+        while !rest.is_empty() {
+            if let Some(after_dot) = rest.strip_prefix('.') {
+                let end = after_dot
+                    .find(|c: char| !is_name_char(c))
+                    .unwrap_or(after_dot.len());
+                if end == 0 {
+                    return None;
+                }
+                parsed.classes.push(after_dot[..end].to_string());
+                rest = &after_dot[end..];
+            } else if let Some(after_bracket) = rest.strip_prefix('[') {
+                let (condition, after) = after_bracket.split_once(']')?;
+                let (name, value) = match condition.split_once('=') {
+                    Some((name, value)) => (name, Some(unquote(value.trim()).to_string())),
+                    None => (condition, None),
+                };
+                let name = name.trim();
+                if name.is_empty() || !name.chars().all(is_name_char) {
+                    return None;
+                }
+                parsed.attributes.push((name.to_string(), value));
+                rest = after;
+            } else {
+                return None;
+            }
+        }
+
+        (parsed.tag.is_some() || !parsed.classes.is_empty() || !parsed.attributes.is_empty())
+            .then_some(parsed)
+    }
+
+    /// Whether an element matches, reading only its static attribute values
+    pub fn matches(&self, tag_name: &str, element: &JSXOpeningElement) -> bool {
+        if self.tag.as_deref().is_some_and(|tag| tag != tag_name) {
+            return false;
+        }
+
+        if !self.classes.is_empty() {
+            let class_name = get_attribute_value(element, "className")
+                .or_else(|| get_attribute_value(element, "class"))
+                .unwrap_or_default();
+            let classes: Vec<&str> = class_name.split_whitespace().collect();
+            if !self
+                .classes
+                .iter()
+                .all(|class| classes.contains(&class.as_str()))
+            {
+                return false;
+            }
+        }
+
+        self.attributes.iter().all(|(name, value)| match value {
+            Some(value) => get_attribute_value(element, name).as_deref() == Some(value.as_str()),
+            None => has_attribute(element, name),
+        })
+    }
+}
+
+/// Strip matching single or double quotes around an attribute value
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|&quote| value.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_core::common::{sync::Lrc, FileName, SourceMap};
+    use swc_core::ecma::ast::{EsVersion, Expr};
+    use swc_core::ecma::parser::{parse_file_as_expr, EsSyntax, Syntax};
+
+    fn parse_opening(src: &str) -> JSXOpeningElement {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(FileName::Anon.into(), src.to_string());
+        let expr = parse_file_as_expr(
+            &fm,
+            Syntax::Es(EsSyntax {
+                jsx: true,
+                ..Default::default()
+            }),
+            EsVersion::latest(),
+            None,
+            &mut vec![],
+        )
+        .expect("failed to parse test input");
+        match *expr {
+            Expr::JSXElement(el) => el.opening,
+            _ => panic!("expected a JSX element"),
+        }
+    }
+
+    fn matches(selector: &str, tag_name: &str, src: &str) -> bool {
+        Selector::parse(selector)
+            .unwrap()
+            .matches(tag_name, &parse_opening(src))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Selector::parse("button.btn-primary[type='submit']"),
+            Some(Selector {
+                tag: Some("button".into()),
+                classes: vec!["btn-primary".into()],
+                attributes: vec![("type".into(), Some("submit".into()))],
+            })
+        );
+        assert_eq!(
+            Selector::parse("[data-cta]"),
+            Some(Selector {
+                tag: None,
+                classes: vec![],
+                attributes: vec![("data-cta".into(), None)],
+            })
+        );
+
+        for invalid in [
+            "",
+            "*",
+            "div > button",
+            "a#main",
+            "button.",
+            "[=x]",
+            "[href^=x]",
+            "[open",
+        ] {
+            assert_eq!(Selector::parse(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_matches_class() {
+        let src = r#"<button className="btn btn-primary" />"#;
+        assert!(matches("button.btn-primary", "button", src));
+        assert!(matches(".btn.btn-primary", "button", src));
+        assert!(!matches("a.btn-primary", "button", src));
+        // Whole class names only
+        assert!(!matches("button.btn-prim", "button", src));
+        // Dynamic class names can't be matched
+        assert!(!matches(
+            "button.btn-primary",
+            "button",
+            "<button className={cls} />"
+        ));
+    }
+
+    #[test]
+    fn test_matches_attributes() {
+        assert!(matches("[data-cta]", "a", "<a data-cta />"));
+        assert!(matches("[data-cta]", "a", "<a data-cta={track} />"));
+        assert!(!matches("[data-cta]", "a", "<a href='/' />"));
+
+        assert!(matches(
+            "input[type=email]",
+            "input",
+            r#"<input type="email" />"#
+        ));
+        assert!(matches(
+            r#"input[type="email"]"#,
+            "input",
+            r#"<input type="email" />"#
+        ));
+        assert!(!matches(
+            "input[type=email]",
+            "input",
+            r#"<input type="text" />"#
+        ));
+        assert!(!matches(
+            "input[type=email]",
+            "input",
+            "<input type={kind} />"
+        ));
+    }
+}
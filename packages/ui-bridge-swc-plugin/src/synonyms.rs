@@ -0,0 +1,184 @@
+//! Locale synonym sets
+//!
+//! Extra synonym groups read from a directory of JSON files, one per locale
+//! (`de.json`, `fr.json`, ...), each holding a list of groups:
+//!
+//! ```json
+//! [{ "triggers": ["speichern"], "synonyms": ["speichern", "sichern", "save"] }]
+//! ```
+//!
+//! A directory is loaded once and cached for the lifetime of the plugin. Reading files
+//! needs the `native` feature, since the SWC WASM sandbox may not allow file access.
+
+use crate::config::PluginConfig;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// A synonym group: text matching any trigger gets all synonyms as aliases
+#[derive(Debug, Clone, Deserialize)]
+pub struct SynonymGroup {
+    pub triggers: Vec<String>,
+    pub synonyms: Vec<String>,
+}
+
+/// Synonym groups by locale
+pub type SynonymSets = HashMap<String, Vec<SynonymGroup>>;
+
+type CachedSets = HashMap<String, Arc<Vec<SynonymGroup>>>;
+
+/// Loaded synonym sets, by directory
+fn cache() -> &'static Mutex<HashMap<String, CachedSets>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, CachedSets>>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// Get the synonym groups of the configured `locale` from `synonym_dir`, if any
+///
+/// A regional locale (`de-AT`) falls back to its language (`de`).
+pub fn locale_synonyms(config: &PluginConfig) -> Option<Arc<Vec<SynonymGroup>>> {
+    let (dir, locale) = (config.synonym_dir.as_deref()?, config.locale.as_deref()?);
+    let locale = locale.to_lowercase();
+
+    let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    let sets = cache
+        .entry(dir.to_string())
+        .or_insert_with(|| prepare(load_dir(dir)));
+
+    sets.get(&locale)
+        .or_else(|| sets.get(locale.split(['-', '_']).next()?))
+        .cloned()
+}
+
+/// Make synonym sets available for `dir` without reading it from disk
+#[cfg(test)]
+pub fn insert_synonym_sets(dir: &str, sets: SynonymSets) {
+    let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    cache.insert(dir.to_string(), prepare(sets));
+}
+
+/// Lowercase locales and entries (aliases are matched in lowercase)
+fn prepare(sets: SynonymSets) -> CachedSets {
+    let lowercase = |words: Vec<String>| words.iter().map(|w| w.to_lowercase()).collect();
+    sets.into_iter()
+        .map(|(locale, groups)| {
+            let groups = groups
+                .into_iter()
+                .map(|group| SynonymGroup {
+                    triggers: lowercase(group.triggers),
+                    synonyms: lowercase(group.synonyms),
+                })
+                .collect();
+            (locale.to_lowercase(), Arc::new(groups))
+        })
+        .collect()
+}
+
+/// Read every `<locale>.json` in `dir`, skipping (with a warning) unreadable files
+#[cfg(feature = "native")]
+fn load_dir(dir: &str) -> SynonymSets {
+    let mut sets = SynonymSets::new();
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!(
+                "[ui-bridge-swc-plugin] Warning: can't read synonymDir \"{}\": {}",
+                dir, err
+            );
+            return sets;
+        }
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(locale) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        let groups = std::fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|err| err.to_string()));
+        match groups {
+            Ok(groups) => {
+                sets.insert(locale.to_string(), groups);
+            }
+            Err(err) => eprintln!(
+                "[ui-bridge-swc-plugin] Warning: skipping synonym file {}: {}",
+                path.display(),
+                err
+            ),
+        }
+    }
+
+    sets
+}
+
+/// Without the `native` feature, only sets inserted in memory are available
+#[cfg(not(feature = "native"))]
+fn load_dir(_dir: &str) -> SynonymSets {
+    SynonymSets::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(triggers: &[&str], synonyms: &[&str]) -> SynonymGroup {
+        SynonymGroup {
+            triggers: triggers.iter().map(|s| s.to_string()).collect(),
+            synonyms: synonyms.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_locale_synonyms() {
+        insert_synonym_sets(
+            "/synonyms/locales",
+            SynonymSets::from([
+                ("de".to_string(), vec![group(&["Speichern"], &["sichern"])]),
+                ("fr".to_string(), vec![group(&["enregistrer"], &["sauver"])]),
+            ]),
+        );
+
+        let config = |locale: &str| PluginConfig {
+            synonym_dir: Some("/synonyms/locales".into()),
+            locale: Some(locale.into()),
+            ..Default::default()
+        };
+
+        let de = locale_synonyms(&config("de")).unwrap();
+        assert_eq!(de[0].triggers, ["speichern"]);
+        let fr = locale_synonyms(&config("fr")).unwrap();
+        assert_eq!(fr[0].synonyms, ["sauver"]);
+
+        // Regional locales fall back to their language
+        let de_at = locale_synonyms(&config("de-AT")).unwrap();
+        assert_eq!(de_at[0].synonyms, ["sichern"]);
+
+        assert!(locale_synonyms(&config("es")).is_none());
+        assert!(locale_synonyms(&PluginConfig::default()).is_none());
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_load_dir() {
+        let dir = std::env::temp_dir().join(format!("ui-bridge-synonyms-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("de.json"),
+            r#"[{ "triggers": ["speichern"], "synonyms": ["sichern"] }]"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("fr.json"), "not json").unwrap();
+        std::fs::write(dir.join("README.md"), "ignored").unwrap();
+
+        let sets = load_dir(dir.to_str().unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets["de"][0].synonyms, ["sichern"]);
+    }
+}
@@ -2,78 +2,321 @@
 //!
 //! Extracts text content from JSX elements and their children.
 
+use crate::config::{HtmlElementPredicate, TextJoinStrategy};
+use std::borrow::Cow;
+use std::ops::Range;
 use swc_core::ecma::ast::*;
 
 /// Extract text content from JSX children
 pub fn extract_text_content(children: &[JSXElementChild]) -> Option<String> {
-    let mut text_parts: Vec<String> = vec![];
+    extract_text_content_with(children, TextJoinStrategy::Concat)
+}
 
-    for child in children {
-        match child {
-            JSXElementChild::JSXText(text) => {
-                let text_str = text.value.as_str();
-                let trimmed = text_str.trim();
-                if !trimmed.is_empty() {
-                    text_parts.push(trimmed.to_string());
-                }
-            }
-            JSXElementChild::JSXExprContainer(expr) => {
-                // Handle string literals in expressions like {"text"}
-                if let JSXExpr::Expr(e) = &expr.expr {
-                    if let Expr::Lit(Lit::Str(s)) = e.as_ref() {
-                        let trimmed = s.value.as_str().trim();
-                        if !trimmed.is_empty() {
-                            text_parts.push(trimmed.to_string());
+/// Extract text content from JSX children, combining separate text nodes
+/// (e.g. `Save<br/>Draft`) as `strategy` says
+pub fn extract_text_content_with(
+    children: &[JSXElementChild],
+    strategy: TextJoinStrategy,
+) -> Option<String> {
+    let mut buffer = TextBuffer::default();
+    buffer.collect(children);
+
+    let TextBuffer {
+        mut text,
+        first,
+        longest,
+    } = buffer;
+    match strategy {
+        _ if text.is_empty() => None,
+        TextJoinStrategy::Concat => Some(text),
+        TextJoinStrategy::First => {
+            text.truncate(first.end);
+            Some(text)
+        }
+        TextJoinStrategy::Longest => {
+            let (range, _) = longest;
+            text.truncate(range.end);
+            text.drain(..range.start);
+            Some(text)
+        }
+    }
+}
+
+/// Text nodes of JSX children in a single buffer, with whitespace collapsed to single spaces
+///
+/// This follows what React renders. JSX trims the lines of a text node, drops the blank
+/// ones and joins the rest with a space, and the browser collapses the remaining runs of
+/// spaces, tabs and newlines, so `Add   to\n    cart` reads as `Add to cart`.
+///
+/// The parser decodes `&nbsp;` to U+00A0, which doesn't separate words: inside a word it's
+/// kept (`Save&nbsp;Draft` stays one word), but it's trimmed from the edges of words, so
+/// `&nbsp;Save` reads as `Save` and a lone `&nbsp;` describes nothing.
+///
+/// Adjacent nodes are always separated by a single space, so `{" "}` spacers between
+/// inline elements (`Read <b>the docs</b>{" "}now`) neither double it nor join the words
+/// around them. The first and longest (first of equally long) node are tracked as ranges
+/// of the buffer, so no per-node strings are allocated.
+#[derive(Default)]
+struct TextBuffer {
+    text: String,
+    first: Range<usize>,
+    /// Range and length in chars
+    longest: (Range<usize>, usize),
+}
+
+impl TextBuffer {
+    fn push(&mut self, part: &str) {
+        let mut words = part
+            .split(|c: char| c.is_ascii_whitespace())
+            .map(str::trim)
+            .filter(|word| !word.is_empty());
+        let Some(first_word) = words.next() else {
+            return;
+        };
+
+        if !self.text.is_empty() {
+            self.text.push(' ');
+        }
+        let start = self.text.len();
+        self.text.push_str(first_word);
+        for word in words {
+            self.text.push(' ');
+            self.text.push_str(word);
+        }
+        let range = start..self.text.len();
+
+        if start == 0 {
+            self.first = range.clone();
+        }
+        let len = self.text[range.clone()].chars().count();
+        if len > self.longest.1 {
+            self.longest = (range, len);
+        }
+    }
+
+    /// Collect the text nodes of JSX children, depth-first
+    fn collect(&mut self, children: &[JSXElementChild]) {
+        for child in children {
+            match child {
+                JSXElementChild::JSXText(text) => self.push(text.value.as_str()),
+                JSXElementChild::JSXExprContainer(expr) => {
+                    // Handle string literals in expressions like {"text"}
+                    if let JSXExpr::Expr(e) = &expr.expr {
+                        if let Expr::Lit(Lit::Str(s)) = e.as_ref() {
+                            self.push(s.value.as_str());
                         }
-                    }
-                    // Handle template literals like {`text`}
-                    if let Expr::Tpl(tpl) = e.as_ref() {
-                        for quasi in &tpl.quasis {
-                            let trimmed = quasi.raw.as_str().trim();
-                            if !trimmed.is_empty() {
-                                text_parts.push(trimmed.to_string());
+                        // Handle template literals like {`text`}
+                        if let Expr::Tpl(tpl) = e.as_ref() {
+                            for quasi in &tpl.quasis {
+                                self.push(quasi.raw.as_str());
                             }
                         }
                     }
                 }
+                // Recursively extract from nested JSX elements (like <span>text</span>)
+                JSXElementChild::JSXElement(el) => self.collect(&el.children),
+                JSXElementChild::JSXFragment(frag) => self.collect(&frag.children),
+                // Spread children (`{...items}`) are only known at runtime, see `has_spread_child`
+                JSXElementChild::JSXSpreadChild(_) => {}
             }
-            // Recursively extract from nested JSX elements (like <span>text</span>)
-            JSXElementChild::JSXElement(el) => {
-                if let Some(text) = extract_text_content(&el.children) {
-                    text_parts.push(text);
-                }
-            }
-            JSXElementChild::JSXFragment(frag) => {
-                if let Some(text) = extract_text_content(&frag.children) {
-                    text_parts.push(text);
-                }
+        }
+    }
+}
+
+/// Check if any (nested) child is a spread child (`{...items}`), whose text can't be
+/// extracted at build time
+pub fn has_spread_child(children: &[JSXElementChild]) -> bool {
+    children.iter().any(|child| match child {
+        JSXElementChild::JSXSpreadChild(_) => true,
+        JSXElementChild::JSXElement(el) => has_spread_child(&el.children),
+        JSXElementChild::JSXFragment(frag) => has_spread_child(&frag.children),
+        _ => false,
+    })
+}
+
+/// Find the first `aria-label` (or failing that, `title`) on a descendant element
+///
+/// Composite controls like `<button><span aria-label="Close dialog" /></button>` carry
+/// their accessible name on a child rather than as text.
+pub fn extract_child_aria_label(children: &[JSXElementChild]) -> Option<String> {
+    find_descendant_attribute(children, "aria-label")
+        .or_else(|| find_descendant_attribute(children, "title"))
+}
+
+fn find_descendant_attribute(children: &[JSXElementChild], attr_name: &str) -> Option<String> {
+    children.iter().find_map(|child| match child {
+        JSXElementChild::JSXElement(el) => get_attribute_value(&el.opening, attr_name)
+            .filter(|value| !value.trim().is_empty())
+            .or_else(|| find_descendant_attribute(&el.children, attr_name)),
+        JSXElementChild::JSXFragment(frag) => find_descendant_attribute(&frag.children, attr_name),
+        _ => None,
+    })
+}
+
+/// Extract the text of a static `dangerouslySetInnerHTML={{ __html: "..." }}`, with its
+/// tags stripped
+///
+/// Elements rendering HTML this way have no JSX children to read text from.
+pub fn extract_inner_html_text(element: &JSXOpeningElement) -> Option<String> {
+    let value = element.attrs.iter().rev().find_map(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(JSXAttr {
+            name: JSXAttrName::Ident(ident),
+            value: Some(JSXAttrValue::JSXExprContainer(container)),
+            ..
+        }) if ident.sym == "dangerouslySetInnerHTML" => match &container.expr {
+            JSXExpr::Expr(expr) => Some(expr.as_ref()),
+            JSXExpr::JSXEmptyExpr(_) => None,
+        },
+        _ => None,
+    })?;
+
+    let Expr::Object(object) = value.unwrap_parens() else {
+        return None;
+    };
+    let html = object.props.iter().find_map(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+            return None;
+        };
+        let Prop::KeyValue(kv) = prop.as_ref() else {
+            return None;
+        };
+        let is_html_key = match &kv.key {
+            PropName::Ident(ident) => ident.sym == "__html",
+            PropName::Str(s) => s.value.as_str() == "__html",
+            _ => false,
+        };
+        if !is_html_key {
+            return None;
+        }
+        match kv.value.as_ref() {
+            Expr::Lit(Lit::Str(s)) => Some(s.value.as_str().to_string()),
+            Expr::Tpl(tpl) => match (&tpl.quasis[..], &tpl.exprs[..]) {
+                ([quasi], []) => Some(quasi.raw.as_str().to_string()),
+                _ => None,
+            },
+            _ => None,
+        }
+    })?;
+
+    let text = strip_html_tags(&html);
+    (!text.is_empty()).then_some(text)
+}
+
+/// Remove HTML tags, separating the text on either side, and collapse whitespace
+fn strip_html_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
             }
+            _ if !in_tag => text.push(c),
             _ => {}
         }
     }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Extract the text of a `<select>`'s selected `<option>`, or its first option
+///
+/// Options nested in `<optgroup>`s are included.
+pub fn extract_select_option_text(children: &[JSXElementChild]) -> Option<String> {
+    let mut options: Vec<&JSXElement> = vec![];
+    collect_options(children, &mut options);
+
+    options
+        .iter()
+        .find(|el| has_attribute(&el.opening, "selected"))
+        .or_else(|| options.first())
+        .and_then(|el| extract_text_content(&el.children))
+}
+
+fn collect_options<'a>(children: &'a [JSXElementChild], options: &mut Vec<&'a JSXElement>) {
+    for child in children {
+        if let JSXElementChild::JSXElement(el) = child {
+            match get_tag_name(&el.opening).as_deref() {
+                Some("option") => options.push(el),
+                Some("optgroup") => collect_options(&el.children, options),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// The child element that captions a container: `<caption>` for a `<table>`,
+/// `<figcaption>` for a `<figure>`
+pub fn caption_tag(tag_name: &str) -> Option<&'static str> {
+    match tag_name {
+        "table" => Some("caption"),
+        "figure" => Some("figcaption"),
+        _ => None,
+    }
+}
+
+/// Extract the text of the first direct child with the caption tag
+pub fn extract_caption_text(children: &[JSXElementChild], caption_tag: &str) -> Option<String> {
+    children
+        .iter()
+        .find_map(|child| match child {
+            JSXElementChild::JSXElement(el)
+                if get_tag_name(&el.opening).as_deref() == Some(caption_tag) =>
+            {
+                Some(el)
+            }
+            _ => None,
+        })
+        .and_then(|el| extract_text_content(&el.children))
+}
+
+/// Extract the text of a `<label>`, leaving out the form controls it wraps (e.g. a
+/// `<select>`'s options)
+pub fn extract_label_text(children: &[JSXElementChild]) -> Option<String> {
+    let is_control = |child: &&JSXElementChild| {
+        matches!(child, JSXElementChild::JSXElement(el)
+            if matches!(get_tag_name(&el.opening).as_deref(), Some("input" | "select" | "textarea" | "button")))
+    };
+    if !children.iter().any(|child| is_control(&child)) {
+        return extract_text_content(children);
+    }
+    let text_children: Vec<JSXElementChild> = children
+        .iter()
+        .filter(|child| !is_control(child))
+        .cloned()
+        .collect();
+    extract_text_content(&text_children)
+}
+
+/// The name of a JSX attribute as written, `ns:name` for a namespaced one (`ui:id`)
+pub fn attribute_name(name: &JSXAttrName) -> Cow<'_, str> {
+    match name {
+        JSXAttrName::Ident(ident) => Cow::Borrowed(ident.sym.as_str()),
+        JSXAttrName::JSXNamespacedName(name) => {
+            Cow::Owned(format!("{}:{}", name.ns.sym, name.name.sym))
+        }
+    }
+}
 
-    if text_parts.is_empty() {
-        None
-    } else {
-        Some(text_parts.join(" "))
+/// Check if a JSX attribute is named `attr_name`, which is `ns:name` for a namespaced one
+fn is_named(name: &JSXAttrName, attr_name: &str) -> bool {
+    match name {
+        JSXAttrName::Ident(ident) => ident.sym.as_str() == attr_name,
+        JSXAttrName::JSXNamespacedName(name) => {
+            attr_name.split_once(':') == Some((name.ns.sym.as_str(), name.name.sym.as_str()))
+        }
     }
 }
 
 /// Get an attribute value from a JSX element as a string
+///
+/// When an attribute is repeated, the last one is read, as it's the one JSX passes on.
+/// Namespaced attributes are named `ns:name`, like everywhere attributes are looked up.
 pub fn get_attribute_value(element: &JSXOpeningElement, attr_name: &str) -> Option<String> {
-    for attr in &element.attrs {
+    for attr in element.attrs.iter().rev() {
         if let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr {
-            let name = match &jsx_attr.name {
-                JSXAttrName::Ident(ident) => ident.sym.as_str(),
-                JSXAttrName::JSXNamespacedName(_ns) => {
-                    // Handle namespaced attributes like aria-label
-                    // They're stored as JSXNamespacedName with ns="aria" and name="label"
-                    // But actually, aria-label is just an Ident with the full name
-                    continue;
-                }
-            };
-
-            if name == attr_name {
+            if is_named(&jsx_attr.name, attr_name) {
                 return match &jsx_attr.value {
                     Some(JSXAttrValue::Lit(Lit::Str(s))) => Some(s.value.as_str().to_string()),
                     Some(JSXAttrValue::JSXExprContainer(expr)) => {
@@ -81,6 +324,12 @@ pub fn get_attribute_value(element: &JSXOpeningElement, attr_name: &str) -> Opti
                             if let Expr::Lit(Lit::Str(s)) = e.as_ref() {
                                 return Some(s.value.as_str().to_string());
                             }
+                            // Handle template literals without interpolations like {`text`}
+                            if let Expr::Tpl(tpl) = e.as_ref() {
+                                if let ([quasi], []) = (&tpl.quasis[..], &tpl.exprs[..]) {
+                                    return Some(quasi.raw.as_str().to_string());
+                                }
+                            }
                         }
                         None
                     }
@@ -95,21 +344,108 @@ pub fn get_attribute_value(element: &JSXOpeningElement, attr_name: &str) -> Opti
 /// Check if element has a specific attribute
 pub fn has_attribute(element: &JSXOpeningElement, attr_name: &str) -> bool {
     element.attrs.iter().any(|attr| {
-        if let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr {
-            if let JSXAttrName::Ident(ident) = &jsx_attr.name {
-                return ident.sym.as_str() == attr_name;
+        matches!(attr, JSXAttrOrSpread::JSXAttr(jsx_attr) if is_named(&jsx_attr.name, attr_name))
+    })
+}
+
+/// Remove all occurrences of an attribute from a JSX element
+pub fn remove_attribute(element: &mut JSXOpeningElement, attr_name: &str) {
+    element.attrs.retain(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(jsx_attr) => !is_named(&jsx_attr.name, attr_name),
+        _ => true,
+    });
+}
+
+/// A boolean attribute as written in JSX
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanAttribute {
+    Absent,
+    /// Bare (`disabled`), `{true}`, or a string other than `"false"`
+    True,
+    /// `{false}` or `"false"`
+    False,
+    /// Any other expression, only known at runtime
+    Dynamic,
+}
+
+impl BooleanAttribute {
+    /// Present and not explicitly false. Dynamic values count, since they may be true.
+    pub fn is_set(self) -> bool {
+        matches!(self, BooleanAttribute::True | BooleanAttribute::Dynamic)
+    }
+}
+
+/// Read a boolean attribute, telling a bare or `true` value from an absent, `false` or
+/// dynamic one (which `get_attribute_value` all report as None). The last of repeated
+/// attributes is read.
+pub fn get_boolean_attribute(element: &JSXOpeningElement, attr_name: &str) -> BooleanAttribute {
+    element
+        .attrs
+        .iter()
+        .rev()
+        .find_map(|attr| match attr {
+            JSXAttrOrSpread::JSXAttr(jsx_attr) if is_named(&jsx_attr.name, attr_name) => {
+                Some(boolean_value(jsx_attr))
             }
+            _ => None,
+        })
+        .unwrap_or(BooleanAttribute::Absent)
+}
+
+fn boolean_value(attr: &JSXAttr) -> BooleanAttribute {
+    let from_str = |s: &str| {
+        if s == "false" {
+            BooleanAttribute::False
+        } else {
+            BooleanAttribute::True
         }
-        false
+    };
+    match &attr.value {
+        None => BooleanAttribute::True,
+        Some(JSXAttrValue::Lit(Lit::Str(s))) => from_str(s.value.as_str()),
+        Some(JSXAttrValue::JSXExprContainer(expr)) => match &expr.expr {
+            JSXExpr::Expr(e) => match e.as_ref() {
+                Expr::Lit(Lit::Bool(b)) if b.value => BooleanAttribute::True,
+                Expr::Lit(Lit::Bool(_)) => BooleanAttribute::False,
+                Expr::Lit(Lit::Str(s)) => from_str(s.value.as_str()),
+                _ => BooleanAttribute::Dynamic,
+            },
+            JSXExpr::JSXEmptyExpr(_) => BooleanAttribute::False,
+        },
+        _ => BooleanAttribute::Dynamic,
+    }
+}
+
+/// Check if element is editable via a truthy `contentEditable`/`contenteditable` attribute
+///
+/// A bare attribute, `"true"`, `""` and `"plaintext-only"` are truthy; `"false"` and
+/// `{false}` are not. Dynamic expressions are treated as editable.
+pub fn is_content_editable(element: &JSXOpeningElement) -> bool {
+    element.attrs.iter().any(|attr| {
+        let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr else {
+            return false;
+        };
+        let JSXAttrName::Ident(ident) = &jsx_attr.name else {
+            return false;
+        };
+        ident.sym.as_str().eq_ignore_ascii_case("contenteditable")
+            && boolean_value(jsx_attr).is_set()
     })
 }
 
 /// Get the tag name from a JSX element
+///
+/// Namespaced elements are returned as `ns:name`, so they are only instrumented when
+/// listed in `elements` (e.g. `"my:widget"`).
 pub fn get_tag_name(element: &JSXOpeningElement) -> Option<String> {
     match &element.name {
         JSXElementName::Ident(ident) => Some(ident.sym.as_str().to_string()),
         JSXElementName::JSXMemberExpr(_) => None, // Skip Component.SubComponent
-        JSXElementName::JSXNamespacedName(_) => None, // Skip namespaced elements
+        JSXElementName::JSXNamespacedName(name) => Some(format!(
+            "{}:{}",
+            name.ns.sym.as_str(),
+            name.name.sym.as_str()
+        )),
     }
 }
 
@@ -122,6 +458,24 @@ pub fn is_html_element(tag_name: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// Check if a tag name is an HTML element (not a React component) as `predicate` says
+///
+/// `html_elements` is the list of HTML tags for `ExplicitList`.
+pub fn is_html_element_with(
+    tag_name: &str,
+    predicate: HtmlElementPredicate,
+    html_elements: &[String],
+) -> bool {
+    match predicate {
+        HtmlElementPredicate::LowercaseFirst => is_html_element(tag_name),
+        HtmlElementPredicate::ExplicitList => html_elements.iter().any(|t| t == tag_name),
+        // Dashed tags are custom elements (`<my-widget>`), instrumented by their own code
+        HtmlElementPredicate::ContainsDashIsCustom => {
+            is_html_element(tag_name) && !tag_name.contains('-')
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,4 +488,348 @@ mod tests {
         assert!(!is_html_element("Button"));
         assert!(!is_html_element("MyComponent"));
     }
+
+    #[test]
+    fn test_is_html_element_with() {
+        let html_elements = vec!["button".to_string(), "input".to_string()];
+        let check = |tag, predicate| is_html_element_with(tag, predicate, &html_elements);
+
+        assert!(check("my-widget", HtmlElementPredicate::LowercaseFirst));
+        assert!(!check("my-widget", HtmlElementPredicate::ExplicitList));
+        assert!(!check(
+            "my-widget",
+            HtmlElementPredicate::ContainsDashIsCustom
+        ));
+
+        assert!(check("button", HtmlElementPredicate::ExplicitList));
+        assert!(!check("dialog", HtmlElementPredicate::ExplicitList));
+        assert!(check("dialog", HtmlElementPredicate::ContainsDashIsCustom));
+        assert!(!check(
+            "MyWidget",
+            HtmlElementPredicate::ContainsDashIsCustom
+        ));
+    }
+
+    fn parse_element(src: &str) -> JSXElement {
+        use swc_core::common::{sync::Lrc, FileName, SourceMap};
+        use swc_core::ecma::parser::{parse_file_as_expr, EsSyntax, Syntax};
+
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(FileName::Anon.into(), src.to_string());
+        let expr = parse_file_as_expr(
+            &fm,
+            Syntax::Es(EsSyntax {
+                jsx: true,
+                ..Default::default()
+            }),
+            EsVersion::latest(),
+            None,
+            &mut vec![],
+        )
+        .expect("failed to parse");
+        match *expr {
+            Expr::JSXElement(el) => *el,
+            _ => panic!("expected a JSX element"),
+        }
+    }
+
+    fn parse_children(src: &str) -> Vec<JSXElementChild> {
+        parse_element(src).children
+    }
+
+    #[test]
+    fn test_get_boolean_attribute() {
+        let attr = |src: &str| get_boolean_attribute(&parse_element(src).opening, "multiple");
+
+        assert_eq!(attr("<select multiple />"), BooleanAttribute::True);
+        assert_eq!(attr("<select multiple={true} />"), BooleanAttribute::True);
+        assert_eq!(
+            attr(r#"<select multiple="multiple" />"#),
+            BooleanAttribute::True
+        );
+        assert_eq!(attr(r#"<select multiple="" />"#), BooleanAttribute::True);
+        assert_eq!(attr("<select multiple={false} />"), BooleanAttribute::False);
+        assert_eq!(
+            attr(r#"<select multiple="false" />"#),
+            BooleanAttribute::False
+        );
+        assert_eq!(
+            attr(r#"<select multiple={"false"} />"#),
+            BooleanAttribute::False
+        );
+        assert_eq!(
+            attr("<select multiple={isMulti} />"),
+            BooleanAttribute::Dynamic
+        );
+        assert_eq!(attr("<select />"), BooleanAttribute::Absent);
+        assert_eq!(attr("<select {...props} />"), BooleanAttribute::Absent);
+
+        assert!(BooleanAttribute::Dynamic.is_set());
+        assert!(!BooleanAttribute::False.is_set());
+        assert!(!BooleanAttribute::Absent.is_set());
+    }
+
+    #[test]
+    fn test_repeated_attributes() {
+        // Like JSX, the last one wins
+        let opening = parse_element(
+            r#"<input placeholder="Name" placeholder="Email" multiple={false} multiple />"#,
+        )
+        .opening;
+        assert_eq!(
+            get_attribute_value(&opening, "placeholder").as_deref(),
+            Some("Email")
+        );
+        assert_eq!(
+            get_boolean_attribute(&opening, "multiple"),
+            BooleanAttribute::True
+        );
+    }
+
+    #[test]
+    fn test_get_attribute_value_template_literal() {
+        let opening = parse_element("<button aria-label={`Close`} />").opening;
+        assert_eq!(
+            get_attribute_value(&opening, "aria-label").as_deref(),
+            Some("Close")
+        );
+
+        // Interpolated templates are dynamic
+        let opening = parse_element("<button aria-label={`Close ${name}`} />").opening;
+        assert_eq!(get_attribute_value(&opening, "aria-label"), None);
+    }
+
+    #[test]
+    fn test_text_buffer() {
+        let mut buffer = TextBuffer::default();
+        buffer.collect(&parse_children(
+            r#"<button>
+        Save <b> all </b> {"your"} <span>unsaved <i>{`drafts`}</i></span>
+        <>to   the</> {`cloud`} {count} <br /> now
+    </button>"#,
+        ));
+        // Every node is written once into the shared buffer, separated by single spaces
+        assert_eq!(buffer.text, "Save all your unsaved drafts to the cloud now");
+        assert_eq!(&buffer.text[buffer.first.clone()], "Save");
+        assert_eq!(&buffer.text[buffer.longest.0.clone()], "unsaved");
+        assert_eq!(buffer.longest.1, 7);
+
+        // Lengths are counted in chars, and ties keep the first node
+        let mut buffer = TextBuffer::default();
+        for part in ["  ", "Größe", "Farben", "Muster"] {
+            buffer.push(part);
+        }
+        assert_eq!(buffer.text, "Größe Farben Muster");
+        assert_eq!(&buffer.text[buffer.first.clone()], "Größe");
+        assert_eq!(&buffer.text[buffer.longest.0.clone()], "Farben");
+    }
+
+    #[test]
+    fn test_whitespace_containers() {
+        let text = |src| extract_text_content(&parse_children(src));
+
+        assert_eq!(
+            text(r#"<a>Read <b>the docs</b>{" "}now</a>"#).as_deref(),
+            Some("Read the docs now")
+        );
+        assert_eq!(
+            text(r#"<a><b>Read</b>{" "}<i>the</i>{" "}{" "}docs{` `}now</a>"#).as_deref(),
+            Some("Read the docs now")
+        );
+        assert_eq!(text(r#"<a>{" "}</a>"#), None);
+
+        // A spacer is no text node of its own
+        let children = parse_children(r#"<a>{" "}<b>Docs</b>{" "}home</a>"#);
+        assert_eq!(
+            extract_text_content_with(&children, TextJoinStrategy::First).as_deref(),
+            Some("Docs")
+        );
+    }
+
+    #[test]
+    fn test_jsx_whitespace() {
+        let text = |src| extract_text_content(&parse_children(src));
+
+        // React's JSX whitespace examples, which all render "Hello World"
+        for src in [
+            "<div>Hello World</div>",
+            "<div>\n  Hello World\n</div>",
+            "<div>\n  Hello\n  World\n</div>",
+            "<div>\n\n  Hello World\n</div>",
+            "<div>\r\n\tHello\r\n\t\tWorld\r\n</div>",
+        ] {
+            assert_eq!(text(src).as_deref(), Some("Hello World"), "{:?}", src);
+        }
+        assert_eq!(text("<div>\n    \n</div>"), None);
+
+        // Entities are decoded, and a non-breaking space is content
+        assert_eq!(
+            text("<button>Save &amp; close</button>").as_deref(),
+            Some("Save & close")
+        );
+        assert_eq!(
+            text("<button>\n  Save&nbsp;Draft  now\n</button>").as_deref(),
+            Some("Save\u{a0}Draft now")
+        );
+        assert_eq!(text("<button>&nbsp;</button>"), None);
+        assert_eq!(text("<button>&nbsp;Save</button>").as_deref(), Some("Save"));
+    }
+
+    #[test]
+    fn test_text_join_strategy() {
+        let children = parse_children("<button>Save<br />all <b>drafts</b></button>");
+        let extract = |strategy| extract_text_content_with(&children, strategy);
+
+        assert_eq!(
+            extract(TextJoinStrategy::Concat).as_deref(),
+            Some("Save all drafts")
+        );
+        assert_eq!(extract(TextJoinStrategy::First).as_deref(), Some("Save"));
+        assert_eq!(
+            extract(TextJoinStrategy::Longest).as_deref(),
+            Some("drafts")
+        );
+        assert_eq!(
+            extract_text_content_with(
+                &parse_children("<button>Save<br />Draft</button>"),
+                TextJoinStrategy::Longest
+            )
+            .as_deref(),
+            Some("Draft")
+        );
+        assert_eq!(
+            extract_text_content_with(&parse_children("<button />"), TextJoinStrategy::First),
+            None
+        );
+    }
+
+    #[test]
+    fn test_multiline_text_collapsed() {
+        let children = parse_children(
+            "<button>\n    Add   to\n        <b>cart\n   and</b>{`  checkout\n  now `}\n</button>",
+        );
+        assert_eq!(
+            extract_text_content(&children).as_deref(),
+            Some("Add to cart and checkout now")
+        );
+
+        // Lengths compare the collapsed text, not the source indentation
+        let children =
+            parse_children("<button>Go\n            to     page<br />Proceed now</button>");
+        assert_eq!(
+            extract_text_content_with(&children, TextJoinStrategy::Longest).as_deref(),
+            Some("Proceed now")
+        );
+        assert_eq!(
+            extract_text_content_with(&children, TextJoinStrategy::First).as_deref(),
+            Some("Go to page")
+        );
+    }
+
+    #[test]
+    fn test_spread_child_text_unresolvable() {
+        let children = parse_children("<button><span>{...labels}</span></button>");
+        assert_eq!(extract_text_content(&children), None);
+        assert!(has_spread_child(&children));
+
+        let children = parse_children("<button>Save</button>");
+        assert!(!has_spread_child(&children));
+    }
+
+    #[test]
+    fn test_extract_inner_html_text() {
+        let inner_html = |src: &str| extract_inner_html_text(&parse_element(src).opening);
+
+        assert_eq!(
+            inner_html(r#"<h1 dangerouslySetInnerHTML={{ __html: "Welcome" }} />"#).as_deref(),
+            Some("Welcome")
+        );
+        assert_eq!(
+            inner_html(
+                r#"<p dangerouslySetInnerHTML={{ "__html": "<b>Terms</b><br/>of use" }} />"#
+            )
+            .as_deref(),
+            Some("Terms of use")
+        );
+        assert_eq!(
+            inner_html("<p dangerouslySetInnerHTML={{ __html: `Hello` }} />").as_deref(),
+            Some("Hello")
+        );
+
+        // Dynamic or empty HTML gives nothing
+        assert_eq!(
+            inner_html("<p dangerouslySetInnerHTML={{ __html: html }} />"),
+            None
+        );
+        assert_eq!(inner_html("<p dangerouslySetInnerHTML={markup} />"), None);
+        assert_eq!(
+            inner_html(r#"<p dangerouslySetInnerHTML={{ __html: "<hr/>" }} />"#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extract_child_aria_label() {
+        let children =
+            parse_children(r#"<button><span><i aria-label="Close dialog" /></span></button>"#);
+        assert_eq!(
+            extract_child_aria_label(&children).as_deref(),
+            Some("Close dialog")
+        );
+
+        // aria-label anywhere beats a closer title
+        let children =
+            parse_children(r#"<button><svg title="Icon" /><span aria-label="Delete" /></button>"#);
+        assert_eq!(
+            extract_child_aria_label(&children).as_deref(),
+            Some("Delete")
+        );
+
+        let children = parse_children(r#"<button><svg title="Settings" /></button>"#);
+        assert_eq!(
+            extract_child_aria_label(&children).as_deref(),
+            Some("Settings")
+        );
+
+        let children = parse_children(r#"<button><span aria-label="" /></button>"#);
+        assert_eq!(extract_child_aria_label(&children), None);
+    }
+
+    #[test]
+    fn test_extract_label_text() {
+        let children = parse_children(r#"<label>Email <input /></label>"#);
+        assert_eq!(extract_label_text(&children).as_deref(), Some("Email"));
+
+        let children = parse_children(
+            r#"<label><b>Country</b><select><option>Canada</option></select></label>"#,
+        );
+        assert_eq!(extract_label_text(&children).as_deref(), Some("Country"));
+
+        let children = parse_children(r#"<label><input /></label>"#);
+        assert_eq!(extract_label_text(&children), None);
+    }
+
+    #[test]
+    fn test_extract_caption_text() {
+        let children = parse_children(
+            r#"<table><caption>Monthly <b>sales</b></caption><tr><td>March</td></tr></table>"#,
+        );
+        assert_eq!(
+            extract_caption_text(&children, "caption").as_deref(),
+            Some("Monthly sales")
+        );
+
+        let children = parse_children(
+            r#"<figure><img src="chart.png" /><figcaption>Revenue</figcaption></figure>"#,
+        );
+        assert_eq!(
+            extract_caption_text(&children, "figcaption").as_deref(),
+            Some("Revenue")
+        );
+
+        // Only direct children caption the container
+        let children =
+            parse_children(r#"<table><tr><td><caption>Nested</caption></td></tr></table>"#);
+        assert_eq!(extract_caption_text(&children, "caption"), None);
+    }
 }
@@ -2,68 +2,611 @@
 //!
 //! Traverses the AST and instruments JSX elements with UI Bridge attributes.
 
-use std::collections::{HashMap, HashSet};
-use swc_core::common::DUMMY_SP;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use swc_core::common::{BytePos, Span, DUMMY_SP};
 use swc_core::ecma::ast::*;
-use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+use swc_core::ecma::visit::{Visit, VisitMut, VisitMutWith, VisitWith};
 
 use crate::alias_generator::{format_aliases, generate_aliases, AliasContext};
-use crate::config::PluginConfig;
-use crate::id_generator::{generate_id, get_semantic_type, IdContext};
+use crate::babel_compat::{self, BabelContext};
+use crate::config::{
+    AttributePlacement, AttributeValueStyle, EmitMode, IdStrategy, InjectedAttribute, PluginConfig,
+    TraversalOrder,
+};
+use crate::id_generator::{
+    file_salt, generate_id, get_semantic_type, href_segment, GeneratedId, IdConfidence, IdContext,
+    TypeContext,
+};
+use crate::roles::{get_role, AttrView};
+use crate::selectors::Selector;
 use crate::text_extractor::{
-    extract_text_content, get_attribute_value, get_tag_name, has_attribute, is_html_element,
+    attribute_name, caption_tag, extract_caption_text, extract_child_aria_label,
+    extract_inner_html_text, extract_label_text, extract_select_option_text, extract_text_content,
+    extract_text_content_with, get_attribute_value, get_boolean_attribute, get_tag_name,
+    has_attribute, has_spread_child, is_content_editable, is_html_element_with, remove_attribute,
 };
 
+/// Why a generated ID is weak
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeakIdReason {
+    /// Nothing described the element, so the ID is only its context and type
+    NoDescriptor,
+    /// The ID collided with an earlier one and got a numeric suffix
+    Collision,
+}
+
+/// An emitted ID that doesn't identify its element well
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeakId {
+    pub id: String,
+    pub reason: WeakIdReason,
+}
+
+impl fmt::Display for WeakId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.reason {
+            WeakIdReason::NoDescriptor => write!(f, "\"{}\" has no descriptor", self.id),
+            WeakIdReason::Collision => write!(f, "\"{}\" needed a collision suffix", self.id),
+        }
+    }
+}
+
+/// Where an ID was emitted, collected for `id_map_path`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdSite {
+    pub id: String,
+    /// Start of the element's opening tag
+    pub pos: BytePos,
+    pub component: Option<String>,
+    /// ID of the nearest instrumented ancestor, known only in top-down traversal
+    pub parent: Option<String>,
+}
+
+/// Attributes an element opted out of via the ignore attribute
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct IgnoredAttributes {
+    id: bool,
+    type_: bool,
+    aliases: bool,
+}
+
+impl IgnoredAttributes {
+    const ALL: Self = Self {
+        id: true,
+        type_: true,
+        aliases: true,
+    };
+
+    /// Parse an ignore value: a comma-separated set of `id`, `type`, `aliases` or `all`
+    ///
+    /// A bare attribute (or one with a dynamic value) ignores everything.
+    fn parse(value: Option<&str>) -> Self {
+        let Some(value) = value else {
+            return Self::ALL;
+        };
+
+        let mut ignored = Self::default();
+        for part in value.split(',').map(|p| p.trim().to_lowercase()) {
+            match part.as_str() {
+                "" | "all" | "true" => return Self::ALL,
+                "id" => ignored.id = true,
+                "type" => ignored.type_ = true,
+                "aliases" => ignored.aliases = true,
+                _ => {}
+            }
+        }
+        ignored
+    }
+
+    fn all(&self) -> bool {
+        *self == Self::ALL
+    }
+}
+
+/// Visitor state scoped to a component, set aside while visiting a component nested in it
+///
+/// Element indices, and so collision suffixes, count from the start of the innermost
+/// enclosing component: adding or removing elements in one component never shifts the IDs
+/// of another. Elements outside any component share the file's top-level scope.
+///
+/// Only IDs carrying their component's name are scoped (see `scopes_by_component`):
+/// content-addressed IDs, IDs without the component name and `legacy_babel_compat` IDs
+/// count indices through the whole file. Collision detection is always file-wide.
+///
+/// Entering a component swaps in a fresh scope with `std::mem::take` and swaps the outer
+/// one back on leaving. Scopes are never cloned, which would turn large files quadratic.
+#[derive(Debug, Default)]
+struct ComponentScope {
+    /// Counter for element indices per tag type
+    element_counters: HashMap<String, usize>,
+}
+
 /// The main AST visitor that instruments JSX elements
 pub struct UIBridgeVisitor {
     config: PluginConfig,
     filename: String,
     /// Stack of component names we're currently inside
     component_stack: Vec<String>,
-    /// Counter for element indices per tag type
-    element_counters: HashMap<String, usize>,
-    /// Set of IDs we've already generated (to detect collisions)
+    /// Names of the components visited, collected when component filters are set
+    seen_components: HashSet<String>,
+    /// State of the innermost enclosing component (see `ComponentScope`)
+    component_scope: ComponentScope,
+    /// Set of IDs we've already generated (to detect collisions). Shared by the whole file,
+    /// so IDs stay unique within it.
     processed_ids: HashSet<String>,
+    /// Stack of `title` attributes on ancestor elements
+    title_stack: Vec<String>,
+    /// Stack of index parameter names of enclosing `.map()` callbacks (None when unnamed)
+    map_index_stack: Vec<Option<String>>,
+    /// Descriptors of enclosing instrumentable elements, tracked for `dedupe_nested_interactive`
+    interactive_stack: Vec<String>,
+    /// Span of the default submit button of each enclosing `<form>` (None when it has none)
+    form_primary_stack: Vec<Option<Span>>,
+    /// Notes explaining why elements got weak IDs, reported in verbose mode
+    diagnostics: Vec<String>,
+    /// IDs written to elements, in the order they were emitted
+    emitted_ids: Vec<String>,
+    /// Where the emitted IDs were written, collected when `id_map_path` is set
+    id_sites: Vec<IdSite>,
+    /// IDs of the enclosing instrumented elements, tracked in top-down traversal
+    ancestor_ids: Vec<String>,
+    /// Parsed `match_selectors` (invalid ones are dropped)
+    match_selectors: Vec<Selector>,
+    /// Number of enclosing elements carrying the `scope_attribute`
+    scope_depth: usize,
+    /// Number of enclosing `<svg>` elements, tracked for `skip_svg`
+    svg_depth: usize,
+    /// Text of enclosing `<label>` elements (None when a label has none)
+    label_stack: Vec<Option<String>>,
+    /// Number of enclosing `createPortal` calls
+    portal_depth: usize,
+    /// Number of enclosing conditional operands (`cond && ...`, `cond ? ... : ...`)
+    conditional_depth: usize,
+    /// Emitted IDs without a descriptor or with a collision suffix
+    weak_ids: Vec<WeakId>,
+    /// Text of the elements with a static `id`, collected up front for `describedby_aliases`
+    element_texts: HashMap<String, String>,
 }
 
 impl UIBridgeVisitor {
     /// Create a new visitor with the given configuration
     pub fn new(config: PluginConfig, filename: String) -> Self {
+        let match_selectors = config
+            .match_selectors
+            .iter()
+            .filter_map(|selector| Selector::parse(selector))
+            .collect();
         Self {
             config,
             filename,
             component_stack: vec![],
-            element_counters: HashMap::new(),
+            seen_components: HashSet::new(),
+            component_scope: ComponentScope::default(),
             processed_ids: HashSet::new(),
+            title_stack: vec![],
+            map_index_stack: vec![],
+            interactive_stack: vec![],
+            form_primary_stack: vec![],
+            diagnostics: vec![],
+            emitted_ids: vec![],
+            id_sites: vec![],
+            ancestor_ids: vec![],
+            match_selectors,
+            scope_depth: 0,
+            svg_depth: 0,
+            label_stack: vec![],
+            portal_depth: 0,
+            conditional_depth: 0,
+            weak_ids: vec![],
+            element_texts: HashMap::new(),
         }
     }
 
+    /// Notes collected while transforming
+    pub fn diagnostics(&self) -> &[String] {
+        &self.diagnostics
+    }
+
+    /// IDs written to elements while transforming
+    pub fn emitted_ids(&self) -> &[String] {
+        &self.emitted_ids
+    }
+
+    /// Where the emitted IDs were written (empty unless `id_map_path` is set)
+    pub fn id_sites(&self) -> &[IdSite] {
+        &self.id_sites
+    }
+
+    /// Emitted IDs that don't identify their element well
+    pub fn weak_ids(&self) -> &[WeakId] {
+        &self.weak_ids
+    }
+
     /// Get the current component name (if any)
     fn current_component(&self) -> Option<&str> {
         self.component_stack.last().map(|s| s.as_str())
     }
 
+    /// In verbose mode, log the component filters that matched no component in the file.
+    /// Filters usually name components of a few files only, so this isn't a diagnostic.
+    fn report_unmatched_component_filters(&self) {
+        if self.config.verbose {
+            for note in self.unmatched_component_filters() {
+                eprintln!("[ui-bridge-swc-plugin] {} in {}", note, self.filename);
+            }
+        }
+    }
+
+    /// The `only_in_components` and `skip_in_components` entries that matched no component
+    /// in the file, which may be misspelled (or defined in other files)
+    fn unmatched_component_filters(&self) -> Vec<String> {
+        let filters = [
+            ("onlyInComponents", &self.config.only_in_components),
+            ("skipInComponents", &self.config.skip_in_components),
+        ];
+        let mut notes = vec![];
+        for (option, entries) in filters {
+            for entry in entries {
+                let component = entry.split_once(':').map_or(entry.as_str(), |(c, _)| c);
+                if !self.seen_components.contains(component) {
+                    notes.push(format!(
+                        "{} entry \"{}\" matched no component (it may be defined in another file)",
+                        option, entry
+                    ));
+                }
+            }
+        }
+        notes
+    }
+
+    /// Whether components get their own `ComponentScope`. Only IDs carrying the component
+    /// name are kept apart by it; the others would collide across components.
+    fn scopes_by_component(&self) -> bool {
+        !self.config.legacy_babel_compat
+            && self.config.include_component_name
+            && self.config.id_strategy == IdStrategy::Semantic
+    }
+
+    /// Visit the body of component `name` in its own `ComponentScope`
+    fn visit_component<N: VisitMutWith<Self>>(&mut self, name: String, n: &mut N) {
+        // Past the maximum depth, a component is treated as part of the enclosing one
+        if self
+            .config
+            .max_component_depth
+            .is_some_and(|max| self.component_stack.len() >= max)
+        {
+            n.visit_mut_children_with(self);
+            return;
+        }
+        if !self.config.only_in_components.is_empty() || !self.config.skip_in_components.is_empty()
+        {
+            self.seen_components.insert(name.clone());
+        }
+        self.component_stack.push(name);
+        let outer = self
+            .scopes_by_component()
+            .then(|| std::mem::take(&mut self.component_scope));
+        n.visit_mut_children_with(self);
+        if let Some(outer) = outer {
+            self.component_scope = outer;
+        }
+        self.component_stack.pop();
+    }
+
+    /// Text that describes an element to a user, as space-separated lowercase words
+    fn interactive_descriptor(n: &JSXElement) -> String {
+        extract_text_content(&n.children)
+            .or_else(|| get_attribute_value(&n.opening, "aria-label"))
+            .unwrap_or_default()
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Whether the nearest instrumentable ancestor is described by the same words as
+    /// `descriptor` (one containing the other), making the two overlapping targets
+    fn overlaps_interactive_ancestor(&self, descriptor: &str) -> bool {
+        let Some(ancestor) = self.interactive_stack.last() else {
+            return false;
+        };
+        if ancestor.is_empty() || descriptor.is_empty() {
+            return false;
+        }
+        let (ancestor, descriptor) = (format!(" {} ", ancestor), format!(" {} ", descriptor));
+        ancestor.contains(&descriptor) || descriptor.contains(&ancestor)
+    }
+
+    /// Whether an `<a>` is left alone for having no href (see `skip_anchors_without_href`)
+    fn skips_anchor(&self, tag_name: &str, has_href: bool) -> bool {
+        self.config.skip_anchors_without_href && tag_name == "a" && !has_href
+    }
+
     /// Get the next element index for a tag type
     fn get_element_index(&mut self, tag_name: &str) -> usize {
-        let counter = self.element_counters.entry(tag_name.to_string()).or_insert(0);
+        let counter = self
+            .component_scope
+            .element_counters
+            .entry(tag_name.to_string())
+            .or_insert(0);
         *counter += 1;
         *counter
     }
 
     /// Add an attribute to a JSX element
     fn add_attribute(&self, element: &mut JSXOpeningElement, name: &str, value: &str) {
-        element.attrs.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
+        let lit = Lit::Str(Str {
             span: DUMMY_SP,
-            name: JSXAttrName::Ident(IdentName {
-                span: DUMMY_SP,
-                sym: name.into(),
-            }),
-            value: Some(JSXAttrValue::Lit(Lit::Str(Str {
+            value: value.into(),
+            raw: None,
+        });
+
+        match self.config.attribute_value_style {
+            AttributeValueStyle::String => {
+                self.inject_attribute(element, name, JSXAttrValue::Lit(lit))
+            }
+            AttributeValueStyle::Expression => {
+                self.add_expression_attribute(element, name, Expr::Lit(lit))
+            }
+        }
+    }
+
+    /// Add an attribute whose value is a JS expression (`name={expr}`)
+    fn add_expression_attribute(&self, element: &mut JSXOpeningElement, name: &str, expr: Expr) {
+        let value = JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            span: DUMMY_SP,
+            expr: JSXExpr::Expr(Box::new(expr)),
+        });
+        self.inject_attribute(element, name, value);
+    }
+
+    /// Insert an attribute where `attribute_placement` says, unless the element already has
+    /// one by that name or holds `max_injected_attributes` attributes
+    fn inject_attribute(&self, element: &mut JSXOpeningElement, name: &str, value: JSXAttrValue) {
+        if self.can_inject(element, name) {
+            let index = self.insertion_index(element);
+            insert_attribute(element, index, name, value);
+        }
+    }
+
+    /// Set `id` to the generated ID. It goes first, so an `id` passed in a spread
+    /// (`{...props}`) still takes precedence.
+    ///
+    /// Every item a `.map()` callback renders gets the same generated ID, so there the
+    /// indices of the enclosing callbacks are appended at runtime
+    /// (`id={"ui-rows-edit-button-" + i}`). Items of a callback without an index parameter
+    /// can't be told apart and get no `id`.
+    fn mirror_to_id(&self, element: &mut JSXOpeningElement, id: &str) {
+        let str_lit = |value: &str| {
+            Box::new(Expr::Lit(Lit::Str(Str {
                 span: DUMMY_SP,
                 value: value.into(),
                 raw: None,
-            }))),
-        }));
+            })))
+        };
+        let separator = &self.config.id_separator;
+
+        let mut value: Option<Box<Expr>> = None;
+        for index_var in &self.map_index_stack {
+            let Some(index_var) = index_var else {
+                return;
+            };
+            let left = match value {
+                None => str_lit(&format!("{}{}", id, separator)),
+                Some(value) => Box::new(Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    op: BinaryOp::Add,
+                    left: value,
+                    right: str_lit(separator),
+                })),
+            };
+            value = Some(Box::new(Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                op: BinaryOp::Add,
+                left,
+                right: Box::new(Expr::Ident(Ident::new_no_ctxt(
+                    index_var.as_str().into(),
+                    DUMMY_SP,
+                ))),
+            })));
+        }
+
+        if self.can_inject(element, "id") {
+            let value = match value {
+                Some(expr) => JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    span: DUMMY_SP,
+                    expr: JSXExpr::Expr(expr),
+                }),
+                None => JSXAttrValue::Lit(Lit::Str(Str {
+                    span: DUMMY_SP,
+                    value: id.into(),
+                    raw: None,
+                })),
+            };
+            insert_attribute(element, 0, "id", value);
+        }
+    }
+
+    /// Whether an attribute may be injected: the element doesn't have one by that name and
+    /// holds fewer than `max_injected_attributes` attributes
+    fn can_inject(&self, element: &JSXOpeningElement, name: &str) -> bool {
+        let skipped = if has_attribute(element, name) {
+            Some("it already has one")
+        } else if self
+            .config
+            .max_injected_attributes
+            .is_some_and(|max| element.attrs.len() >= max)
+        {
+            Some("it has reached maxInjectedAttributes")
+        } else {
+            None
+        };
+        if let Some(reason) = skipped {
+            if self.config.verbose {
+                eprintln!(
+                    "[ui-bridge-swc-plugin] Warning: not adding {} to <{}> in {}: {}",
+                    name,
+                    get_tag_name(element).unwrap_or_default(),
+                    self.filename,
+                    reason
+                );
+            }
+        }
+        skipped.is_none()
+    }
+
+    /// Where the next injected attribute goes, per `attribute_placement`
+    ///
+    /// Attributes injected earlier (or already present under one of the plugin's names) are
+    /// skipped, so injected attributes keep their order.
+    fn insertion_index(&self, element: &JSXOpeningElement) -> usize {
+        let start = match self.config.attribute_placement {
+            AttributePlacement::Append => return element.attrs.len(),
+            AttributePlacement::Prepend => 0,
+            AttributePlacement::AfterStatic => element
+                .attrs
+                .iter()
+                .rposition(|attr| {
+                    matches!(
+                        attr_name(attr).as_deref(),
+                        Some("className" | "class" | "id" | "style")
+                    )
+                })
+                .map_or(0, |index| index + 1),
+        };
+        element.attrs[start..]
+            .iter()
+            .position(|attr| !attr_name(attr).is_some_and(|name| self.is_own_attribute(&name)))
+            .map_or(element.attrs.len(), |offset| start + offset)
+    }
+
+    /// Read an attribute to infer the ID, type or aliases from, unless
+    /// `ignore_attributes_for_inference` lists it
+    fn inference_attr(&self, element: &JSXOpeningElement, name: &str) -> Option<String> {
+        if self.ignored_for_inference(name) {
+            return None;
+        }
+        get_attribute_value(element, name)
+    }
+
+    /// The type `classname_type_map` gives the first of the element's static `className`
+    /// tokens it lists
+    fn class_type(&self, element: &JSXOpeningElement) -> Option<&str> {
+        if self.config.classname_type_map.is_empty() {
+            return None;
+        }
+        let class_name = self.inference_attr(element, "className")?;
+        class_name
+            .split_whitespace()
+            .find_map(|token| self.config.classname_type_map.get(token))
+            .map(String::as_str)
+    }
+
+    /// Whether an element has an attribute to infer from (see `inference_attr`)
+    fn has_inference_attr(&self, element: &JSXOpeningElement, name: &str) -> bool {
+        !self.ignored_for_inference(name) && has_attribute(element, name)
+    }
+
+    fn ignored_for_inference(&self, name: &str) -> bool {
+        self.config
+            .ignore_attributes_for_inference
+            .iter()
+            .any(|ignored| ignored == name)
+    }
+
+    /// Whether an attribute name is one the plugin injects
+    fn is_own_attribute(&self, name: &str) -> bool {
+        [
+            &self.config.id_attribute,
+            &self.config.aliases_attribute,
+            &self.config.type_attribute,
+            &self.config.role_attribute,
+            &self.config.text_attribute,
+            &self.config.index_key_attribute,
+            &self.config.confidence_attribute,
+            &self.config.primary_attribute,
+            &self.config.portal_attribute,
+            &self.config.group_attribute,
+            &self.config.compact_attribute,
+        ]
+        .iter()
+        .any(|own| own.as_str() == name)
+    }
+
+    /// Whether a call is `createPortal(...)` or `ReactDOM.createPortal(...)`
+    fn is_portal_call(call: &CallExpr) -> bool {
+        let Callee::Expr(callee) = &call.callee else {
+            return false;
+        };
+        match callee.as_ref() {
+            Expr::Ident(ident) => ident.sym == "createPortal",
+            Expr::Member(member) => {
+                matches!(&member.prop, MemberProp::Ident(prop) if prop.sym == "createPortal")
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the index parameter name if this call is `something.map(callback)`
+    ///
+    /// Returns `Some(None)` for a `.map()` callback without an index parameter.
+    fn map_callback_index_param(call: &CallExpr) -> Option<Option<String>> {
+        let Callee::Expr(callee) = &call.callee else {
+            return None;
+        };
+        let Expr::Member(member) = callee.as_ref() else {
+            return None;
+        };
+        if !matches!(&member.prop, MemberProp::Ident(prop) if prop.sym == "map") {
+            return None;
+        }
+
+        let index_pat = match call.args.first().map(|arg| arg.expr.as_ref()) {
+            Some(Expr::Arrow(arrow)) => arrow.params.get(1),
+            Some(Expr::Fn(f)) => f.function.params.get(1).map(|p| &p.pat),
+            _ => return None,
+        };
+
+        Some(match index_pat {
+            Some(Pat::Ident(ident)) => Some(ident.id.sym.as_str().to_string()),
+            _ => None,
+        })
+    }
+
+    /// Find a form's primary action: its default submit button, i.e. the first
+    /// `<input type="submit">` or `<button>` without another type, as browsers pick it
+    ///
+    /// Nested forms are not searched. The action is identified by its span, so a form whose
+    /// action has no real position (e.g. created by an earlier transform) has none.
+    fn find_primary_action(children: &[JSXElementChild]) -> Option<Span> {
+        Self::find_submit(children)
+            .map(|el| el.span)
+            .filter(|span| !span.is_dummy())
+    }
+
+    /// The first submit button among `children` and their descendants
+    fn find_submit(children: &[JSXElementChild]) -> Option<&JSXElement> {
+        children.iter().find_map(|child| match child {
+            JSXElementChild::JSXElement(el) => {
+                let input_type = get_attribute_value(&el.opening, "type");
+                let is_submit = match get_tag_name(&el.opening).as_deref() {
+                    Some("button") => input_type.as_deref().is_none_or(|t| t == "submit"),
+                    Some("input") => matches!(input_type.as_deref(), Some("submit" | "image")),
+                    Some("form") => return None,
+                    _ => false,
+                };
+                if is_submit {
+                    Some(&**el)
+                } else {
+                    Self::find_submit(&el.children)
+                }
+            }
+            JSXElementChild::JSXFragment(frag) => Self::find_submit(&frag.children),
+            _ => None,
+        })
     }
 
     /// Check if a name looks like a React component (starts with uppercase)
@@ -71,6 +614,28 @@ impl UIBridgeVisitor {
         name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false)
     }
 
+    /// The component a JSX element renders, if it is one (`Card`, or `CardBody` for
+    /// `<Card.Body>`)
+    fn rendered_component(opening: &JSXOpeningElement) -> Option<String> {
+        fn object_name(obj: &JSXObject) -> String {
+            match obj {
+                JSXObject::Ident(ident) => ident.sym.to_string(),
+                JSXObject::JSXMemberExpr(member) => {
+                    object_name(&member.obj) + member.prop.sym.as_str()
+                }
+            }
+        }
+
+        let name = match &opening.name {
+            JSXElementName::Ident(ident) => ident.sym.to_string(),
+            JSXElementName::JSXMemberExpr(member) => {
+                object_name(&member.obj) + member.prop.sym.as_str()
+            }
+            JSXElementName::JSXNamespacedName(_) => return None,
+        };
+        Self::is_component_name(&name).then_some(name)
+    }
+
     /// Process a JSX element
     fn process_jsx_element(&mut self, n: &mut JSXElement) {
         // Get tag name
@@ -79,35 +644,193 @@ impl UIBridgeVisitor {
             None => return, // Skip member expressions
         };
 
-        // Only instrument lowercase HTML elements
-        if !is_html_element(&tag_name) {
+        // Only instrument HTML elements
+        if !is_html_element_with(
+            &tag_name,
+            self.config.html_element_predicate,
+            &self.config.html_elements,
+        ) {
+            return;
+        }
+
+        // Read and strip the explicit label
+        let label = get_attribute_value(&n.opening, &self.config.label_attribute);
+        remove_attribute(&mut n.opening, &self.config.label_attribute);
+
+        // Read the opt-out attribute. It's kept in the output, as only it tells instrumenting
+        // again that the missing attributes were left out on purpose.
+        let ignored = if has_attribute(&n.opening, &self.config.ignore_attribute) {
+            let value = get_attribute_value(&n.opening, &self.config.ignore_attribute);
+            IgnoredAttributes::parse(value.as_deref())
+        } else {
+            IgnoredAttributes::default()
+        };
+        if ignored.all() {
             return;
         }
 
         // Check if should instrument
-        if !self.config.should_instrument(&tag_name) {
+        let content_editable = is_content_editable(&n.opening);
+        let detected = self.config.detect_contenteditable && content_editable;
+        let in_svg = self.svg_depth > 0;
+        let svg_allowed = in_svg && self.config.svg_instrument_elements.contains(&tag_name);
+        if !(self.config.should_instrument(&tag_name) || detected || svg_allowed) {
             return;
         }
 
-        // Skip if already has data-ui-id
-        if self.config.skip_existing && has_attribute(&n.opening, &self.config.id_attribute) {
+        if self.config.skip_conditional && self.conditional_depth > 0 {
+            return;
+        }
+
+        // Inside an <svg>, only the allowlisted tags are instrumented
+        if in_svg && !svg_allowed {
+            return;
+        }
+
+        // Outside any scope, nothing is instrumented
+        if self.config.scope_attribute.is_some() && self.scope_depth == 0 {
+            return;
+        }
+
+        let has_href = has_attribute(&n.opening, "href");
+        if self.skips_anchor(&tag_name, has_href) {
+            return;
+        }
+
+        // An empty list matches everything, a list of only invalid selectors nothing
+        if !self.config.match_selectors.is_empty()
+            && !self
+                .match_selectors
+                .iter()
+                .any(|selector| selector.matches(&tag_name, &n.opening))
+        {
+            return;
+        }
+
+        // Only the outermost of nested interactive elements (e.g. `<a><button>Go</button></a>`)
+        // is instrumented, so they don't become ambiguous overlapping targets
+        if self.config.dedupe_nested_interactive
+            && self.overlaps_interactive_ancestor(&Self::interactive_descriptor(n))
+        {
             return;
         }
 
+        // Skip if already has data-ui-id
+        let mut previous_id = None;
+        if has_attribute(&n.opening, &self.config.id_attribute) {
+            let value = get_attribute_value(&n.opening, &self.config.id_attribute);
+            if self.config.skip_existing && (value.is_some() || self.config.skip_existing_dynamic) {
+                return;
+            }
+            // Replace it with a generated one. A static value still describes the element,
+            // while a dynamic one can't be read at build time.
+            previous_id = value;
+            remove_attribute(&mut n.opening, &self.config.id_attribute);
+        }
+
         // Check component filters
-        if self.config.should_skip_component(self.current_component()) {
+        if self
+            .config
+            .should_skip_component(self.current_component(), &tag_name)
+        {
+            return;
+        }
+
+        // Extract context for ID generation. A select's children are its options, which
+        // describe the choices rather than the control, so only the selected one is used.
+        // Likewise, a table or figure is described by its caption, not all of its content.
+        let (text_content, option_text) = if tag_name == "select" {
+            (None, extract_select_option_text(&n.children))
+        } else if let Some(caption_tag) = caption_tag(&tag_name) {
+            (extract_caption_text(&n.children, caption_tag), None)
+        } else {
+            let inner_html = || {
+                self.config
+                    .extract_inner_html
+                    .then(|| extract_inner_html_text(&n.opening))
+                    .flatten()
+            };
+            (
+                extract_text_content_with(&n.children, self.config.text_join_strategy)
+                    .or_else(inner_html),
+                None,
+            )
+        };
+        // A form control wrapped in a `<label>` is described by the label's text
+        let text_content = text_content.or_else(|| {
+            matches!(tag_name.as_str(), "input" | "select" | "textarea")
+                .then(|| self.label_stack.last().cloned().flatten())
+                .flatten()
+        });
+        let aria_label = self.inference_attr(&n.opening, "aria-label").or_else(|| {
+            // Fall back to a label carried by a child (e.g. an icon) when there's no text
+            (self.config.derive_from_child_aria && text_content.is_none() && option_text.is_none())
+                .then(|| extract_child_aria_label(&n.children))
+                .flatten()
+        });
+        let placeholder = self.inference_attr(&n.opening, "placeholder");
+        let title = self.inference_attr(&n.opening, "title");
+        let name = self.inference_attr(&n.opening, "name");
+        // An empty `alt` marks a decorative image, which it doesn't describe
+        let alt = self.inference_attr(&n.opening, "alt");
+        let alt_text = alt.as_deref().filter(|alt| !alt.is_empty());
+        let static_id = self.inference_attr(&n.opening, "id");
+        let existing_id = static_id.clone().or(previous_id);
+        let test_id = self.inference_attr(&n.opening, "data-testid");
+        let href = self.inference_attr(&n.opening, "href");
+        let input_type = self.inference_attr(&n.opening, "type");
+        // React spells it `inputMode`
+        let input_mode = self
+            .inference_attr(&n.opening, "inputMode")
+            .or_else(|| self.inference_attr(&n.opening, "inputmode"));
+        let value = self.inference_attr(&n.opening, "value");
+        let group_label = (tag_name == "optgroup")
+            .then(|| self.inference_attr(&n.opening, "label"))
+            .flatten();
+        // Radios share a `name` on purpose, their value is what tells them apart
+        let is_radio = tag_name == "input" && input_type.as_deref() == Some("radio");
+        let radio_value = value.as_deref().filter(|_| is_radio);
+        // A button without text can be described by its `submit` or `reset` type
+        let button_type =
+            (self.config.describe_button_type && tag_name == "button" && text_content.is_none())
+                .then_some(input_type.as_deref())
+                .flatten()
+                .filter(|t| matches!(*t, "submit" | "reset"));
+        let (aria_haspopup, has_aria_controls) = if self.config.detect_triggers {
+            (
+                self.inference_attr(&n.opening, "aria-haspopup"),
+                self.has_inference_attr(&n.opening, "aria-controls"),
+            )
+        } else {
+            (None, false)
+        };
+
+        // Its ID would only say where the element is, and churn as siblings come and go
+        let href_segment = href.as_deref().and_then(href_segment);
+        if self.config.skip_fully_dynamic
+            && [
+                &label,
+                &text_content,
+                &option_text,
+                &aria_label,
+                &placeholder,
+                &title,
+                &name,
+                &existing_id,
+                &test_id,
+                &value,
+                &group_label,
+            ]
+            .iter()
+            .all(|source| source.is_none())
+            && href_segment.is_none()
+            && alt_text.is_none()
+        {
             return;
         }
 
-        // Extract context for ID generation
-        let text_content = extract_text_content(&n.children);
-        let aria_label = get_attribute_value(&n.opening, "aria-label");
-        let placeholder = get_attribute_value(&n.opening, "placeholder");
-        let title = get_attribute_value(&n.opening, "title");
-        let name = get_attribute_value(&n.opening, "name");
-        let existing_id = get_attribute_value(&n.opening, "id");
-        let input_type = get_attribute_value(&n.opening, "type");
         let element_index = self.get_element_index(&tag_name);
+        let ancestor_title = self.title_stack.last().cloned();
 
         // Generate ID
         let id_ctx = IdContext {
@@ -119,46 +842,322 @@ impl UIBridgeVisitor {
             placeholder: placeholder.as_deref(),
             title: title.as_deref(),
             existing_id: existing_id.as_deref(),
+            test_id: test_id.as_deref(),
+            label: label.as_deref(),
+            option_text: option_text.as_deref(),
+            name: name.as_deref(),
+            radio_value,
+            value: value.as_deref(),
+            group_label: group_label.as_deref(),
+            alt: alt_text,
+            href_segment,
+            ancestor_title: ancestor_title.as_deref(),
+            button_type,
             element_index,
         };
 
-        let generated_id = generate_id(&self.config, &id_ctx);
+        // The Babel plugin only reads direct text and the element's own attributes
+        let (babel_text, babel_aria_label, babel_id) = if self.config.legacy_babel_compat {
+            (
+                babel_compat::extract_text_content(&n.children),
+                self.inference_attr(&n.opening, "aria-label"),
+                self.inference_attr(&n.opening, "id"),
+            )
+        } else {
+            (None, None, None)
+        };
+        // An author's static `id`, taken as the ID with `use_existing_id_verbatim`
+        let verbatim_id =
+            static_id.filter(|id| self.config.use_existing_id_verbatim && !id.trim().is_empty());
+        let babel_ctx = self.config.legacy_babel_compat.then(|| BabelContext {
+            component_name: self.component_stack.last().map(String::as_str),
+            file_path: &self.filename,
+            tag_name: &tag_name,
+            text_content: babel_text.as_deref(),
+            aria_label: babel_aria_label.as_deref(),
+            placeholder: placeholder.as_deref(),
+            title: title.as_deref(),
+            name: name.as_deref(),
+            existing_id: babel_id.as_deref(),
+            // Babel counts from zero
+            element_index: element_index - 1,
+        });
+
+        let GeneratedId {
+            id: generated_id,
+            mut confidence,
+            descriptor,
+        } = match (&babel_ctx, verbatim_id) {
+            (Some(babel_ctx), _) => babel_compat::generate_id(&self.config, babel_ctx),
+            (None, Some(id)) => {
+                let id = if self.config.prefix_verbatim_ids {
+                    format!(
+                        "{}{}{}",
+                        self.config.id_prefix_for(self.current_component()),
+                        self.config.id_separator,
+                        id
+                    )
+                } else {
+                    id
+                };
+                GeneratedId {
+                    descriptor: Some(id.clone()),
+                    id,
+                    confidence: IdConfidence::High,
+                }
+            }
+            (None, None) => generate_id(&self.config, &id_ctx),
+        };
 
-        // Handle ID collisions
-        let final_id = if self.processed_ids.contains(&generated_id) {
-            format!("{}-{}", generated_id, element_index)
+        // Handle ID collisions. Reserved IDs are disambiguated the same way.
+        let collided = self.processed_ids.contains(&generated_id)
+            || self.config.reserved_ids.contains(&generated_id);
+        let final_id = if collided {
+            // The suffix depends on the element's position
+            confidence = IdConfidence::Low;
+            match &babel_ctx {
+                Some(babel_ctx) => format!("{}-{}", generated_id, babel_ctx.element_index),
+                None => {
+                    // The suffix is salted with the file, so it's unique across files too
+                    let salt = self.config.guarantee_global_unique.then(|| {
+                        format!("{}{}", self.config.id_separator, file_salt(&self.filename))
+                    });
+                    // Indices restart in each component, so a component sharing another's
+                    // name could reach a suffix already taken: move past it
+                    let mut index = element_index;
+                    let id = loop {
+                        let id = format!(
+                            "{}{}{}{}",
+                            generated_id,
+                            self.config.id_separator,
+                            self.config.format_index(index),
+                            salt.as_deref().unwrap_or_default()
+                        );
+                        if !self.processed_ids.contains(&id)
+                            && !self.config.reserved_ids.contains(&id)
+                        {
+                            break id;
+                        }
+                        index += 1;
+                    };
+                    self.processed_ids.insert(id.clone());
+                    id
+                }
+            }
         } else {
             self.processed_ids.insert(generated_id.clone());
             generated_id
         };
 
-        // Add data-ui-id
-        self.add_attribute(&mut n.opening, &self.config.id_attribute, &final_id);
+        let class_type = self.class_type(&n.opening);
+        let semantic_type = match &babel_ctx {
+            Some(babel_ctx) => babel_compat::get_semantic_type(&tag_name, babel_ctx.text_content),
+            None => get_semantic_type(&TypeContext {
+                tag_name: &tag_name,
+                input_type: input_type.as_deref(),
+                input_mode: input_mode.as_deref(),
+                placeholder: placeholder.as_deref(),
+                name: name.as_deref(),
+                has_href,
+                content_editable,
+                aria_haspopup: aria_haspopup.as_deref(),
+                has_aria_controls,
+                button_type,
+                class_type,
+            }),
+        };
 
-        // Add data-ui-type
-        let semantic_type = get_semantic_type(
-            &tag_name,
-            input_type.as_deref(),
-            placeholder.as_deref(),
-            name.as_deref(),
-        );
-        self.add_attribute(&mut n.opening, &self.config.type_attribute, &semantic_type);
+        if text_content.is_none() && has_spread_child(&n.children) {
+            self.diagnostics.push(format!(
+                "<{}> \"{}\" is text-unresolvable: a spread child ({{...}}) prevents text extraction",
+                tag_name, final_id
+            ));
+        }
 
-        // Generate and add aliases
-        if self.config.generate_aliases {
+        // Resolve `aria-describedby` (a list of IDs) to the text of the referenced elements
+        let description = self
+            .config
+            .describedby_aliases
+            .then(|| self.inference_attr(&n.opening, "aria-describedby"))
+            .flatten()
+            .map(|ids| {
+                ids.split_whitespace()
+                    .filter_map(|id| self.element_texts.get(id).map(String::as_str))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .filter(|description| !description.is_empty());
+
+        // Generate aliases
+        let aliases_str = if self.config.emits(InjectedAttribute::Aliases) && !ignored.aliases {
             let alias_ctx = AliasContext {
-                tag_name: &tag_name,
-                text_content: text_content.as_deref(),
+                label: label.as_deref(),
+                // A select's selected option stands in for its text
+                text_content: text_content.as_deref().or(option_text.as_deref()),
                 aria_label: aria_label.as_deref(),
                 placeholder: placeholder.as_deref(),
                 title: title.as_deref(),
                 name: name.as_deref(),
+                alt: alt_text,
+                ancestor_title: ancestor_title.as_deref(),
+                description: description.as_deref(),
+            };
+
+            let mut aliases = match &babel_ctx {
+                Some(babel_ctx) => babel_compat::generate_aliases(&self.config, babel_ctx),
+                None => generate_aliases(&self.config, &alias_ctx),
+            };
+            // The ID already carries its descriptor, so only the other search terms are kept
+            if let Some(descriptor) = descriptor.as_deref() {
+                if self.config.alias_excludes_id_descriptor {
+                    let descriptor = descriptor.replace('-', " ");
+                    aliases.retain(|alias| alias.replace('-', " ") != descriptor);
+                }
+            }
+            Some(format_aliases(&aliases, self.config.max_aliases_bytes))
+                .filter(|formatted| !formatted.is_empty())
+        } else {
+            None
+        };
+
+        let emit_id = !ignored.id && self.config.emits(InjectedAttribute::Id);
+        let emit_type = !ignored.type_ && self.config.emits(InjectedAttribute::Type);
+        match self.config.emit_mode {
+            EmitMode::Separate => {
+                // Add data-ui-id
+                if emit_id {
+                    self.add_attribute(&mut n.opening, &self.config.id_attribute, &final_id);
+                }
+
+                // Add data-ui-type
+                if emit_type {
+                    self.add_attribute(&mut n.opening, &self.config.type_attribute, &semantic_type);
+                }
+            }
+            EmitMode::Compact => {
+                // Add a single data-ui="id|type|aliases", leaving parts not emitted empty
+                let compact = [
+                    if emit_id { &final_id } else { "" },
+                    if emit_type { &semantic_type } else { "" },
+                    aliases_str.as_deref().unwrap_or(""),
+                ]
+                .join(&self.config.compact_delimiter);
+                self.add_attribute(&mut n.opening, &self.config.compact_attribute, &compact);
+            }
+        }
+        if self.config.mirror_to_id && emit_id {
+            self.mirror_to_id(&mut n.opening, &final_id);
+        }
+        if emit_id {
+            let reason = if descriptor.is_none() {
+                Some(WeakIdReason::NoDescriptor)
+            } else if collided {
+                Some(WeakIdReason::Collision)
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                self.weak_ids.push(WeakId {
+                    id: final_id.clone(),
+                    reason,
+                });
+            }
+            self.emitted_ids.push(final_id.clone());
+            if self.config.id_map_path.is_some() {
+                self.id_sites.push(IdSite {
+                    id: final_id.clone(),
+                    pos: n.opening.span.lo,
+                    component: self.component_stack.last().cloned(),
+                    parent: self.ancestor_ids.last().cloned(),
+                });
+            }
+        }
+
+        // Add data-ui-role
+        if self.config.emits(InjectedAttribute::Role) {
+            let explicit_role = self.inference_attr(&n.opening, "role");
+            let attrs = AttrView {
+                input_type: input_type.as_deref(),
+                has_href,
+                has_list: self.has_inference_attr(&n.opening, "list"),
+                // `multiple={false}` leaves a plain dropdown
+                multiple: !self.ignored_for_inference("multiple")
+                    && get_boolean_attribute(&n.opening, "multiple").is_set(),
+                size: self
+                    .inference_attr(&n.opening, "size")
+                    .and_then(|size| size.parse().ok()),
+                alt: alt.as_deref(),
+                has_accessible_name: ["aria-label", "aria-labelledby", "title"]
+                    .iter()
+                    .any(|attr| self.has_inference_attr(&n.opening, attr)),
             };
+            if let Some(role) = get_role(&tag_name, explicit_role.as_deref(), &attrs) {
+                self.add_attribute(&mut n.opening, &self.config.role_attribute, &role);
+            }
+        }
+
+        // Add data-ui-text, the same text the ID was derived from
+        if self.config.emits(InjectedAttribute::Text) {
+            if let Some(text) = &text_content {
+                self.add_attribute(&mut n.opening, &self.config.text_attribute, text);
+            }
+        }
+
+        // Add data-ui-group
+        let group = name
+            .as_deref()
+            .filter(|_| is_radio && self.config.emits(InjectedAttribute::Group));
+        if let Some(group) = group {
+            self.add_attribute(&mut n.opening, &self.config.group_attribute, group);
+        }
+
+        // Add data-ui-primary
+        if self.config.emits(InjectedAttribute::Primary)
+            && self.form_primary_stack.last() == Some(&Some(n.span))
+        {
+            self.add_attribute(&mut n.opening, &self.config.primary_attribute, "true");
+        }
+
+        // Add data-ui-portal
+        if self.config.emits(InjectedAttribute::Portal) && self.portal_depth > 0 {
+            self.add_attribute(&mut n.opening, &self.config.portal_attribute, "true");
+        }
+
+        // Add data-ui-confidence
+        if self.config.emits(InjectedAttribute::Confidence) && emit_id {
+            self.add_attribute(
+                &mut n.opening,
+                &self.config.confidence_attribute,
+                confidence.as_str(),
+            );
+        }
+
+        // Elements rendered by a `.map()` callback with nothing static to describe them
+        // collide, so expose the loop index to tell them apart at runtime
+        let has_static_descriptor = label.is_some()
+            || text_content.is_some()
+            || aria_label.is_some()
+            || placeholder.is_some()
+            || title.is_some()
+            || existing_id.is_some()
+            || test_id.is_some()
+            || radio_value.is_some();
+        if self.config.emits(InjectedAttribute::IndexKey) && !has_static_descriptor {
+            if let Some(Some(index_var)) = self.map_index_stack.last() {
+                let index_ident =
+                    Expr::Ident(Ident::new_no_ctxt(index_var.as_str().into(), DUMMY_SP));
+                self.add_expression_attribute(
+                    &mut n.opening,
+                    &self.config.index_key_attribute,
+                    index_ident,
+                );
+            }
+        }
 
-            let aliases = generate_aliases(&self.config, &alias_ctx);
-            if !aliases.is_empty() {
-                let aliases_str = format_aliases(&aliases);
-                self.add_attribute(&mut n.opening, &self.config.aliases_attribute, &aliases_str);
+        // Add aliases
+        if self.config.emit_mode == EmitMode::Separate {
+            if let Some(aliases_str) = &aliases_str {
+                self.add_attribute(&mut n.opening, &self.config.aliases_attribute, aliases_str);
             }
         }
 
@@ -171,29 +1170,178 @@ impl UIBridgeVisitor {
     }
 }
 
-impl VisitMut for UIBridgeVisitor {
-    // Track function declarations (function MyComponent() {})
-    fn visit_mut_fn_decl(&mut self, n: &mut FnDecl) {
-        let name = n.ident.sym.as_str().to_string();
-        if Self::is_component_name(&name) {
-            self.component_stack.push(name);
-            n.visit_mut_children_with(self);
-            self.component_stack.pop();
-        } else {
-            n.visit_mut_children_with(self);
+/// The name of a (non-spread) attribute, `ns:name` for a namespaced one
+fn attr_name(attr: &JSXAttrOrSpread) -> Option<Cow<'_, str>> {
+    match attr {
+        JSXAttrOrSpread::JSXAttr(jsx_attr) => Some(attribute_name(&jsx_attr.name)),
+        JSXAttrOrSpread::SpreadElement(_) => None,
+    }
+}
+
+/// Insert a `name=value` attribute into a JSX element at `index`. A `ns:name` name gives
+/// a namespaced attribute.
+fn insert_attribute(
+    element: &mut JSXOpeningElement,
+    index: usize,
+    name: &str,
+    value: JSXAttrValue,
+) {
+    let ident = |sym: &str| IdentName {
+        span: DUMMY_SP,
+        sym: sym.into(),
+    };
+    let name = match name.split_once(':') {
+        Some((ns, local)) => JSXAttrName::JSXNamespacedName(JSXNamespacedName {
+            span: DUMMY_SP,
+            ns: ident(ns),
+            name: ident(local),
+        }),
+        None => JSXAttrName::Ident(ident(name)),
+    };
+    element.attrs.insert(
+        index,
+        JSXAttrOrSpread::JSXAttr(JSXAttr {
+            span: DUMMY_SP,
+            name,
+            value: Some(value),
+        }),
+    );
+}
+
+/// Find already-instrumented elements that instrumenting again would change, described as
+/// `<tag> "id"`
+///
+/// Each element is re-processed on its own, so only the skip logic is exercised.
+#[cfg(debug_assertions)]
+pub fn self_check(config: &PluginConfig, filename: &str, program: &Program) -> Vec<String> {
+    use swc_core::common::EqIgnoreSpan;
+
+    struct SelfCheck {
+        visitor: UIBridgeVisitor,
+        changed: Vec<String>,
+    }
+
+    impl Visit for SelfCheck {
+        fn visit_jsx_element(&mut self, n: &JSXElement) {
+            let mut again = n.clone();
+            self.visitor.process_jsx_element(&mut again);
+            if !again.opening.eq_ignore_span(&n.opening) {
+                let tag_name = get_tag_name(&n.opening).unwrap_or_default();
+                let id = get_attribute_value(&n.opening, &self.visitor.config.id_attribute);
+                self.changed
+                    .push(format!("<{}> \"{}\"", tag_name, id.unwrap_or_default()));
+            }
+            n.visit_children_with(self);
         }
     }
 
-    // Track variable declarations with arrow functions (const MyComponent = () => {})
-    fn visit_mut_var_declarator(&mut self, n: &mut VarDeclarator) {
-        if let Pat::Ident(ident) = &n.name {
-            let name = ident.id.sym.as_str().to_string();
+    let mut check = SelfCheck {
+        visitor: UIBridgeVisitor::new(config.clone(), filename.to_string()),
+        changed: vec![],
+    };
+    program.visit_with(&mut check);
+    check.changed
+}
+
+/// IDs carried by more than one element of a transformed program, as `"id" (n times)`
+///
+/// Covers every element, including ones that were instrumented before and skipped.
+pub fn duplicate_ids(config: &PluginConfig, program: &Program) -> Vec<String> {
+    struct Ids<'a> {
+        config: &'a PluginConfig,
+        counts: BTreeMap<String, usize>,
+    }
+
+    impl Visit for Ids<'_> {
+        fn visit_jsx_opening_element(&mut self, n: &JSXOpeningElement) {
+            let id = match self.config.emit_mode {
+                EmitMode::Separate => get_attribute_value(n, &self.config.id_attribute),
+                EmitMode::Compact => get_attribute_value(n, &self.config.compact_attribute)
+                    .and_then(|compact| {
+                        compact
+                            .split(self.config.compact_delimiter.as_str())
+                            .next()
+                            .map(String::from)
+                    }),
+            };
+            if let Some(id) = id.filter(|id| !id.is_empty()) {
+                *self.counts.entry(id).or_default() += 1;
+            }
+            n.visit_children_with(self);
+        }
+    }
+
+    let mut ids = Ids {
+        config,
+        counts: BTreeMap::new(),
+    };
+    program.visit_with(&mut ids);
+    ids.counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(id, count)| format!("\"{}\" ({} times)", id, count))
+        .collect()
+}
+
+/// Collect the text (or else the `aria-label`) of every element with a static `id`
+fn element_texts<N: VisitWith<ElementTexts>>(node: &N) -> HashMap<String, String> {
+    let mut texts = ElementTexts::default();
+    node.visit_with(&mut texts);
+    texts.0
+}
+
+#[derive(Default)]
+struct ElementTexts(HashMap<String, String>);
+
+impl Visit for ElementTexts {
+    fn visit_jsx_element(&mut self, n: &JSXElement) {
+        if let Some(id) = get_attribute_value(&n.opening, "id") {
+            let text = extract_text_content(&n.children)
+                .or_else(|| get_attribute_value(&n.opening, "aria-label"));
+            if let Some(text) = text {
+                self.0.entry(id).or_insert(text);
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+impl VisitMut for UIBridgeVisitor {
+    // Collect what `aria-describedby` may reference before instrumenting anything
+    fn visit_mut_module(&mut self, n: &mut Module) {
+        if self.config.describedby_aliases {
+            self.element_texts = element_texts(n);
+        }
+        n.visit_mut_children_with(self);
+        self.report_unmatched_component_filters();
+    }
+
+    fn visit_mut_script(&mut self, n: &mut Script) {
+        if self.config.describedby_aliases {
+            self.element_texts = element_texts(n);
+        }
+        n.visit_mut_children_with(self);
+        self.report_unmatched_component_filters();
+    }
+
+    // Track function declarations (function MyComponent() {})
+    fn visit_mut_fn_decl(&mut self, n: &mut FnDecl) {
+        let name = n.ident.sym.as_str().to_string();
+        if Self::is_component_name(&name) {
+            self.visit_component(name, n);
+        } else {
+            n.visit_mut_children_with(self);
+        }
+    }
+
+    // Track variable declarations with arrow functions (const MyComponent = () => {})
+    fn visit_mut_var_declarator(&mut self, n: &mut VarDeclarator) {
+        if let Pat::Ident(ident) = &n.name {
+            let name = ident.id.sym.as_str().to_string();
             if Self::is_component_name(&name) {
                 if let Some(init) = &n.init {
                     if matches!(init.as_ref(), Expr::Arrow(_) | Expr::Fn(_)) {
-                        self.component_stack.push(name);
-                        n.visit_mut_children_with(self);
-                        self.component_stack.pop();
+                        self.visit_component(name, n);
                         return;
                     }
                 }
@@ -212,27 +1360,189 @@ impl VisitMut for UIBridgeVisitor {
     fn visit_mut_class_decl(&mut self, n: &mut ClassDecl) {
         let name = n.ident.sym.as_str().to_string();
         if Self::is_component_name(&name) {
-            self.component_stack.push(name);
-            n.visit_mut_children_with(self);
-            self.component_stack.pop();
+            self.visit_component(name, n);
         } else {
             n.visit_mut_children_with(self);
         }
     }
 
+    // Track `.map()` callbacks so list items can expose their index
+    fn visit_mut_call_expr(&mut self, n: &mut CallExpr) {
+        if Self::is_portal_call(n) {
+            self.portal_depth += 1;
+            n.visit_mut_children_with(self);
+            self.portal_depth -= 1;
+            return;
+        }
+        match Self::map_callback_index_param(n) {
+            Some(index_param) => {
+                self.map_index_stack.push(index_param);
+                n.visit_mut_children_with(self);
+                self.map_index_stack.pop();
+            }
+            None => n.visit_mut_children_with(self),
+        }
+    }
+
+    // Track operands rendered only when a condition holds (`cond && <a />`, `a ?? <b />`)
+    fn visit_mut_bin_expr(&mut self, n: &mut BinExpr) {
+        if !matches!(
+            n.op,
+            BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing
+        ) {
+            n.visit_mut_children_with(self);
+            return;
+        }
+        n.left.visit_mut_with(self);
+        self.conditional_depth += 1;
+        n.right.visit_mut_with(self);
+        self.conditional_depth -= 1;
+    }
+
+    // Track the branches of a ternary (`cond ? <a /> : <b />`)
+    fn visit_mut_cond_expr(&mut self, n: &mut CondExpr) {
+        n.test.visit_mut_with(self);
+        self.conditional_depth += 1;
+        n.cons.visit_mut_with(self);
+        n.alt.visit_mut_with(self);
+        self.conditional_depth -= 1;
+    }
+
     // Process JSX elements
     fn visit_mut_jsx_element(&mut self, n: &mut JSXElement) {
-        // Visit children first (depth-first)
-        n.visit_mut_children_with(self);
+        // Text is extracted from the (unmodified) children either way, so the order only
+        // changes which of a parent and its descendants claims an ID first
+        let top_down = self.config.traversal_order == TraversalOrder::TopDown;
+        let mut pushed_id = false;
+        if top_down {
+            let emitted = self.emitted_ids.len();
+            self.process_jsx_element(n);
+            // Make this element's ID available to its descendants
+            if self.emitted_ids.len() > emitted {
+                self.ancestor_ids.extend(self.emitted_ids.last().cloned());
+                pushed_id = true;
+            }
+        }
+
+        // Make this element's title available to its descendants
+        let title = if self.config.inherit_ancestor_title {
+            self.inference_attr(&n.opening, "title")
+        } else {
+            None
+        };
+        let pushed_title = title.is_some();
+        self.title_stack.extend(title);
+
+        // Make a label's text available to the form controls it wraps
+        let is_label = get_tag_name(&n.opening).as_deref() == Some("label");
+        if is_label {
+            self.label_stack.push(extract_label_text(&n.children));
+        }
 
-        // Then process this element
-        self.process_jsx_element(n);
+        // Find the primary action of a form before its descendants are processed
+        let is_form = self.config.emits(InjectedAttribute::Primary)
+            && get_tag_name(&n.opening).as_deref() == Some("form");
+        if is_form {
+            self.form_primary_stack
+                .push(Self::find_primary_action(&n.children));
+        }
+
+        // Open a scope for the descendants of a scope element
+        let opens_scope = self
+            .config
+            .scope_attribute
+            .as_deref()
+            .is_some_and(|attr| has_attribute(&n.opening, attr));
+        if opens_scope {
+            self.scope_depth += 1;
+        }
+
+        let opens_svg = self.config.skip_svg && get_tag_name(&n.opening).as_deref() == Some("svg");
+        if opens_svg {
+            self.svg_depth += 1;
+        }
+
+        // Make this element's descriptor available to nested interactive elements
+        let interactive = self.config.dedupe_nested_interactive
+            && get_tag_name(&n.opening).is_some_and(|tag| {
+                self.config.should_instrument(&tag)
+                    && !self.skips_anchor(&tag, has_attribute(&n.opening, "href"))
+            });
+        if interactive {
+            self.interactive_stack.push(Self::interactive_descriptor(n));
+        }
+
+        // Visit children (depth-first). JSX passed to a component keeps the name of the
+        // component it's written in, unless configured to take the receiving one's
+        let receiver = if self.config.children_inherit_parent_component {
+            None
+        } else {
+            Self::rendered_component(&n.opening)
+        };
+        match receiver {
+            Some(name) => self.visit_component(name, n),
+            None => n.visit_mut_children_with(self),
+        }
+
+        if interactive {
+            self.interactive_stack.pop();
+        }
+        if is_form {
+            self.form_primary_stack.pop();
+        }
+        if is_label {
+            self.label_stack.pop();
+        }
+        if opens_scope {
+            self.scope_depth -= 1;
+        }
+        if opens_svg {
+            self.svg_depth -= 1;
+        }
+        if pushed_title {
+            self.title_stack.pop();
+        }
+        if pushed_id {
+            self.ancestor_ids.pop();
+        }
+
+        if !top_down {
+            self.process_jsx_element(n);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{DescriptorSource, Environment, HtmlElementPredicate};
+    use swc_core::common::{sync::Lrc, FileName, SourceMap};
+    use swc_core::ecma::codegen::to_code;
+    use swc_core::ecma::parser::{parse_file_as_module, EsSyntax, Syntax};
+
+    /// Parse `src` as a JSX module
+    fn parse(src: &str) -> Module {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(FileName::Anon.into(), src.to_string());
+        parse_file_as_module(
+            &fm,
+            Syntax::Es(EsSyntax {
+                jsx: true,
+                ..Default::default()
+            }),
+            EsVersion::latest(),
+            None,
+            &mut vec![],
+        )
+        .expect("failed to parse test input")
+    }
+
+    /// Parse `src` as JSX, run the visitor over it and print the result
+    fn transform(config: PluginConfig, src: &str) -> String {
+        let mut module = parse(src);
+        module.visit_mut_with(&mut UIBridgeVisitor::new(config, "Test.tsx".into()));
+        to_code(&module)
+    }
 
     #[test]
     fn test_is_component_name() {
@@ -241,4 +1551,1932 @@ mod tests {
         assert!(!UIBridgeVisitor::is_component_name("button"));
         assert!(!UIBridgeVisitor::is_component_name("myComponent"));
     }
+
+    #[test]
+    fn test_string_attribute_values() {
+        let out = transform(
+            PluginConfig::default(),
+            "function Toolbar() { return <button>Save</button>; }",
+        );
+        assert!(out.contains(r#"data-ui-id="ui-toolbar-save-button""#));
+        assert!(out.contains(r#"data-ui-type="button""#));
+    }
+
+    #[test]
+    fn test_expression_attribute_values() {
+        let config = PluginConfig {
+            attribute_value_style: AttributeValueStyle::Expression,
+            ..Default::default()
+        };
+        let out = transform(
+            config,
+            "function Toolbar() { return <button>Save</button>; }",
+        );
+        assert!(out.contains(r#"data-ui-id={"ui-toolbar-save-button"}"#));
+        assert!(out.contains(r#"data-ui-type={"button"}"#));
+    }
+
+    #[test]
+    fn test_extract_inner_html() {
+        let src = r#"function Hero() {
+            return <a href="/start" dangerouslySetInnerHTML={{ __html: "<b>Welcome</b> aboard" }} />;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-hero-link""#));
+
+        let config = PluginConfig {
+            extract_inner_html: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-hero-welcome-aboard-link""#));
+    }
+
+    #[test]
+    fn test_attribute_placement() {
+        let src = r#"function Toolbar() {
+            return <button onClick={save} className="btn" style={s} onBlur={blur}>Save</button>;
+        }"#;
+        let with = |attribute_placement| {
+            let config = PluginConfig {
+                attribute_placement,
+                ..Default::default()
+            };
+            transform(config, src)
+        };
+        let attrs = r#"data-ui-id="ui-toolbar-save-button" data-ui-type="button" data-ui-aliases="save,store,keep,persist,apply""#;
+
+        assert!(with(AttributePlacement::Append).contains(&format!(
+            r#"<button onClick={{save}} className="btn" style={{s}} onBlur={{blur}} {}>"#,
+            attrs
+        )));
+        assert!(with(AttributePlacement::Prepend).contains(&format!(
+            r#"<button {} onClick={{save}} className="btn" style={{s}} onBlur={{blur}}>"#,
+            attrs
+        )));
+        // Between the static attributes and the handlers that follow them
+        assert!(with(AttributePlacement::AfterStatic).contains(&format!(
+            r#"<button onClick={{save}} className="btn" style={{s}} {} onBlur={{blur}}>"#,
+            attrs
+        )));
+
+        // Without static attributes they come first
+        let config = PluginConfig {
+            attribute_placement: AttributePlacement::AfterStatic,
+            ..Default::default()
+        };
+        let out = transform(
+            config,
+            "function Toolbar() { return <button onClick={save}>Save</button>; }",
+        );
+        assert!(out.contains(r#"<button data-ui-id="ui-toolbar-save-button""#));
+        assert!(out.contains(r#"data-ui-aliases="save,store,keep,persist,apply" onClick={save}>"#));
+    }
+
+    #[test]
+    fn test_member_expression_fragment_children_instrumented() {
+        let out = transform(
+            PluginConfig::default(),
+            "function Toolbar() { return <React.Fragment><button>Save</button></React.Fragment>; }",
+        );
+        assert!(out.contains(r#"data-ui-id="ui-toolbar-save-button""#));
+        assert!(!out.contains("<React.Fragment data-ui-id"));
+    }
+
+    #[test]
+    fn test_inherit_ancestor_title() {
+        let src =
+            r#"function Toolbar() { return <span title="Delete"><button>🗑</button></span>; }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-toolbar-button""#));
+
+        let config = PluginConfig {
+            inherit_ancestor_title: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-toolbar-delete-button""#));
+        assert!(out.contains(r#"data-ui-aliases="delete""#));
+    }
+
+    #[test]
+    fn test_id_and_alias_word_limits() {
+        let src = r#"function Signup() { return <button>Create my new team account</button>; }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-signup-create-my-new-team-button""#));
+        assert!(out.contains(r#"data-ui-aliases="create my new team account"#));
+
+        let config = PluginConfig {
+            id_max_words: 8,
+            alias_max_words: Some(2),
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-signup-create-my-new-team-account-button""#));
+        assert!(out.contains(r#"data-ui-aliases="create my"#));
+        assert!(!out.contains("create my new"));
+    }
+
+    #[test]
+    fn test_contenteditable() {
+        let src = r#"function Editor() {
+            return <>
+                <div contentEditable="true" aria-label="Comment"></div>
+                <div contentEditable={false}></div>
+                <textarea contentEditable placeholder="Notes" />
+            </>;
+        }"#;
+
+        // Not instrumented unless detection is enabled, but typed when instrumented anyway
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains("ui-editor-comment-div"));
+        assert!(out.contains(r#"data-ui-type="richtext""#));
+
+        let config = PluginConfig {
+            detect_contenteditable: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-editor-comment-div" data-ui-type="richtext""#));
+        assert_eq!(out.matches("-div\"").count(), 1);
+    }
+
+    #[test]
+    fn test_select_and_textarea_descriptors() {
+        let src = r#"function Filters() {
+            return <>
+                <select><option>Any color</option><option>Red</option></select>
+                <select>
+                    <optgroup label="Sizes"><option>Small</option><option selected>Medium</option></optgroup>
+                </select>
+                <select aria-label="Country"><option>France</option></select>
+                <textarea placeholder="Leave a comment" />
+            </>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-filters-any-color-dropdown""#));
+        assert!(out.contains(r#"data-ui-id="ui-filters-medium-dropdown""#));
+        assert!(out.contains(r#"data-ui-id="ui-filters-country-dropdown""#));
+        assert!(out.contains(r#"data-ui-id="ui-filters-leave-a-comment-textarea""#));
+
+        // The selected option is searchable too
+        let out = transform(
+            PluginConfig::default(),
+            r#"function Filters() {
+                return <select aria-label="Country"><option>France</option><option>Spain</option></select>;
+            }"#,
+        );
+        assert!(
+            out.contains(r#"data-ui-aliases="france,country""#),
+            "{}",
+            out
+        );
+    }
+
+    #[test]
+    fn test_role_attribute() {
+        let src = r#"function Nav() {
+            return <>
+                <a href="/home">Home</a>
+                <a onClick={open}>Open</a>
+                <button role="tab">Details</button>
+            </>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains("data-ui-role"));
+
+        let config = PluginConfig {
+            emit_role_attribute: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out
+            .contains(r#"data-ui-id="ui-nav-home-link" data-ui-type="link" data-ui-role="link""#));
+        assert!(
+            out.contains(r#"data-ui-id="ui-nav-open-link" data-ui-type="text" data-ui-aliases"#)
+        );
+        assert!(out.contains(r#"data-ui-type="button" data-ui-role="tab""#));
+
+        // Roles that depend on other attributes
+        let config = PluginConfig {
+            emit_role_attribute: true,
+            generate_aliases: false,
+            ..Default::default()
+        };
+        let out = transform(
+            config,
+            r#"function Filters() {
+                return <><select multiple name="tags" /><input list="cities" name="city" /></>;
+            }"#,
+        );
+        assert!(out.contains(r#"data-ui-type="dropdown" data-ui-role="listbox""#));
+        assert!(out.contains(r#"data-ui-type="address-input" data-ui-role="combobox""#));
+
+        let out = transform(
+            PluginConfig {
+                emit_role_attribute: true,
+                ..Default::default()
+            },
+            r#"function Filters() { return <select multiple={false} name="tags" />; }"#,
+        );
+        assert!(out.contains(r#"data-ui-role="combobox""#));
+    }
+
+    #[test]
+    fn test_traversal_order() {
+        let src = r#"function Menu() { return <a href="/"><a href="/">Home</a></a>; }"#;
+
+        // Bottom-up: the inner link claims the base ID first
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"<a href="/" data-ui-id="ui-menu-home-link-2""#));
+        assert!(out.contains(r#"<a href="/" data-ui-id="ui-menu-home-link" "#));
+        assert!(out.find("link-2").unwrap() < out.find("link\" ").unwrap());
+
+        // Top-down: the outer link is assigned before its children are visited
+        let config = PluginConfig {
+            traversal_order: TraversalOrder::TopDown,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.find("link\" ").unwrap() < out.find("link-2").unwrap());
+    }
+
+    #[test]
+    fn test_top_down_parent_ids() {
+        let src = r#"function Login() {
+            return <form>
+                <fieldset aria-label="Account"><input placeholder="Email" /></fieldset>
+                <button>Sign In</button>
+            </form>;
+        }"#;
+        let parents = |order| {
+            let config = PluginConfig {
+                id_map_path: Some("ids".into()),
+                additional_elements: vec!["fieldset".into()],
+                traversal_order: order,
+                ..Default::default()
+            };
+            let mut visitor = UIBridgeVisitor::new(config, "test.tsx".into());
+            parse(src).visit_mut_with(&mut visitor);
+            visitor
+                .id_sites()
+                .iter()
+                .map(|site| (site.id.clone(), site.parent.clone()))
+                .collect::<HashMap<_, _>>()
+        };
+
+        // Top-down: every element sees the ID of its nearest instrumented ancestor
+        let top_down = parents(TraversalOrder::TopDown);
+        assert_eq!(top_down["ui-login-sign-in-form"], None);
+        assert_eq!(
+            top_down["ui-login-account-fieldset"].as_deref(),
+            Some("ui-login-sign-in-form")
+        );
+        assert_eq!(
+            top_down["ui-login-email-input"].as_deref(),
+            Some("ui-login-account-fieldset")
+        );
+        assert_eq!(
+            top_down["ui-login-sign-in-button"].as_deref(),
+            Some("ui-login-sign-in-form")
+        );
+
+        // Bottom-up: children are instrumented before their ancestors have IDs
+        let bottom_up = parents(TraversalOrder::BottomUp);
+        assert!(bottom_up.values().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_map_index_key() {
+        let src = r#"function Links({ items }) {
+            return <nav>
+                {items.map((item, i) => <a href={item.href}>{item.label}</a>)}
+                {items.map(function (item) { return <button>{item.label}</button>; })}
+                {items.map((item, idx) => <a href={item.href}>Static</a>)}
+            </nav>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains("data-ui-index-key"));
+
+        let config = PluginConfig {
+            emit_index_key: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(
+            out.contains(r#"data-ui-id="ui-links-link" data-ui-type="link" data-ui-index-key={i}"#)
+        );
+        assert_eq!(out.matches("data-ui-index-key").count(), 1);
+    }
+
+    #[test]
+    fn test_ignored_attributes_parse() {
+        assert!(IgnoredAttributes::parse(None).all());
+        assert!(IgnoredAttributes::parse(Some("all")).all());
+        assert!(IgnoredAttributes::parse(Some("aliases, all")).all());
+        assert_eq!(
+            IgnoredAttributes::parse(Some("id, Aliases")),
+            IgnoredAttributes {
+                id: true,
+                type_: false,
+                aliases: true
+            }
+        );
+        assert_eq!(
+            IgnoredAttributes::parse(Some("false")),
+            IgnoredAttributes::default()
+        );
+    }
+
+    #[test]
+    fn test_ignore_attribute() {
+        let src = r#"function Toolbar() {
+            return <>
+                <button data-ui-ignore="aliases">Save</button>
+                <button data-ui-ignore="id,type">Cancel</button>
+                <button data-ui-ignore>Delete</button>
+            </>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(
+            r#"<button data-ui-ignore="aliases" data-ui-id="ui-toolbar-save-button" data-ui-type="button">"#
+        ));
+        assert!(out.contains(
+            r#"<button data-ui-ignore="id,type" data-ui-aliases="cancel,close,dismiss,exit,abort">"#
+        ));
+        assert!(out.contains("<button data-ui-ignore>Delete</button>"));
+    }
+
+    #[test]
+    fn test_label_attribute() {
+        let src = r#"function Hero() {
+            return <button data-ui-label="Primary Action"><Icon /><span className="sr-only">Go</span></button>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains("data-ui-label"));
+        assert!(out.contains(r#"data-ui-id="ui-hero-primary-action-button""#));
+        assert!(out.contains(r#"data-ui-aliases="primary action,go""#));
+    }
+
+    #[test]
+    fn test_namespaced_elements() {
+        let src = r#"function Panel() {
+            return <svg:g><my:widget>Open</my:widget><svg:rect /></svg:g>;
+        }"#;
+
+        // Skipped unless explicitly listed
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains("data-ui-id"));
+
+        let config = PluginConfig {
+            additional_elements: vec!["my:widget".into()],
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(
+            r#"<my:widget data-ui-id="ui-panel-open-my-widget" data-ui-type="my:widget""#
+        ));
+        assert!(out.contains("<svg:rect/>"));
+    }
+
+    #[test]
+    fn test_environment() {
+        let src = r#"function Login() {
+            return <form>
+                <button type="submit">Sign In</button>
+                <input type="radio" name="plan" value="pro" />
+            </form>;
+        }"#;
+        let attributes = |out: &str| {
+            let mut names: Vec<String> = out
+                .split(|c: char| c.is_whitespace() || c == '<')
+                .filter_map(|token| token.split_once('=').map(|(name, _)| name.to_string()))
+                .filter(|name| name.starts_with("data-ui"))
+                .collect();
+            names.sort();
+            names.dedup();
+            names
+        };
+
+        // Development emits every attribute, whatever the other options say
+        let config = PluginConfig {
+            environment: Some(Environment::Development),
+            generate_aliases: false,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert_eq!(
+            attributes(&out),
+            [
+                "data-ui-aliases",
+                "data-ui-confidence",
+                "data-ui-group",
+                "data-ui-id",
+                "data-ui-primary",
+                "data-ui-role",
+                "data-ui-text",
+                "data-ui-type",
+            ]
+        );
+
+        // Production only emits the configured subset of what's enabled
+        let config = PluginConfig {
+            environment: Some(Environment::Production),
+            emit_text_attribute: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert_eq!(attributes(&out), ["data-ui-id"]);
+        assert!(out.contains(r#"data-ui-id="ui-login-sign-in-button""#));
+
+        let config = PluginConfig {
+            environment: Some(Environment::Production),
+            production_attributes: vec![
+                InjectedAttribute::Id,
+                InjectedAttribute::Type,
+                InjectedAttribute::Role,
+            ],
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert_eq!(attributes(&out), ["data-ui-id", "data-ui-type"]);
+
+        // In compact mode, parts that aren't emitted are left empty
+        let config = PluginConfig {
+            environment: Some(Environment::Production),
+            emit_mode: EmitMode::Compact,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui="ui-login-sign-in-button||""#));
+    }
+
+    #[test]
+    fn test_compact_emit_mode() {
+        let src = r#"function Login() {
+            return <>
+                <button>Sign In</button>
+                <input type="email" data-ui-ignore="aliases" />
+            </>;
+        }"#;
+
+        let config = PluginConfig {
+            emit_mode: EmitMode::Compact,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(
+            r#"data-ui="ui-login-sign-in-button|button|sign in,signin,log in,login,authenticate""#
+        ));
+        assert!(out.contains(r#"data-ui="ui-login-input|email-input|""#));
+        assert!(!out.contains("data-ui-id"));
+        assert!(!out.contains("data-ui-type"));
+        assert!(!out.contains("data-ui-aliases"));
+
+        let config = PluginConfig {
+            emit_mode: EmitMode::Compact,
+            compact_delimiter: ";".into(),
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui="ui-login-input;email-input;""#));
+    }
+
+    #[test]
+    fn test_skip_existing_dynamic() {
+        let src = r#"function Row({ rowId }) {
+            return <>
+                <button data-ui-id="static-save">Save</button>
+                <button data-ui-id={rowId}>Edit</button>
+            </>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"<button data-ui-id="static-save">"#));
+        assert!(out.contains("<button data-ui-id={rowId}>"));
+
+        let config = PluginConfig {
+            skip_existing_dynamic: false,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"<button data-ui-id="static-save">"#));
+        assert!(!out.contains("rowId}>"));
+        assert!(out.contains(r#"<button data-ui-id="ui-row-edit-button""#));
+    }
+
+    #[test]
+    fn test_regenerate_existing_ids() {
+        let src = r#"function LoginForm() {
+            return <form>
+                <button data-ui-id="ui-login-form-submit-button">Sign in</button>
+                <button data-ui-id="reset">Clear</button>
+            </form>;
+        }"#;
+        let config = PluginConfig {
+            skip_existing: false,
+            ..Default::default()
+        };
+
+        let out = transform(config.clone(), src);
+        assert!(out.contains(r#"<button data-ui-id="ui-login-form-submit-button" data-ui-type"#));
+        assert!(out.contains(r#"<button data-ui-id="ui-login-form-reset-button" data-ui-type"#));
+        assert_eq!(out.matches("data-ui-id=").count(), 3);
+
+        // Regenerating from the output keeps the same IDs
+        let again = transform(config, &out);
+        assert!(again.contains(r#"data-ui-id="ui-login-form-submit-button""#));
+        assert!(again.contains(r#"data-ui-id="ui-login-form-reset-button""#));
+        assert_eq!(again.matches("data-ui-id=").count(), 3);
+    }
+
+    #[test]
+    fn test_confidence_attribute() {
+        let src = r#"function Cart() {
+            return <>
+                <button data-testid="checkout">Pay</button>
+                <button>Save</button>
+                <button>Save</button>
+                <input />
+            </>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains("data-ui-confidence"));
+
+        let config = PluginConfig {
+            emit_confidence: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(
+            r#"data-testid="checkout" data-ui-id="ui-cart-pay-button" data-ui-type="button" data-ui-confidence="high""#
+        ));
+        assert!(out.contains(
+            r#"data-ui-id="ui-cart-save-button" data-ui-type="button" data-ui-confidence="medium""#
+        ));
+        assert!(out.contains(
+            r#"data-ui-id="ui-cart-save-button-3" data-ui-type="button" data-ui-confidence="low""#
+        ));
+        assert!(out.contains(
+            r#"data-ui-id="ui-cart-input" data-ui-type="input" data-ui-confidence="low""#
+        ));
+    }
+
+    #[test]
+    fn test_derive_from_child_aria() {
+        let src = r#"function Dialog() {
+            return <>
+                <button onClick={close}><span aria-label="Close dialog" /></button>
+                <button><svg title="Settings" /></button>
+                <button aria-label="Help"><i aria-label="Question mark" /></button>
+            </>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains("close-dialog-button"));
+
+        let config = PluginConfig {
+            derive_from_child_aria: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-dialog-close-dialog-button""#));
+        assert!(out.contains(r#"data-ui-id="ui-dialog-settings-button""#));
+        assert!(out.contains(r#"data-ui-id="ui-dialog-help-button""#));
+    }
+
+    #[test]
+    fn test_dedupe_nested_interactive() {
+        let src = r#"function Hero() {
+            return <>
+                <a href="/start"><button>Go</button></a>
+                <a href="/docs">Read the <button>docs</button></a>
+                <a href="/help"><button>Contact us</button></a>
+                <button>Save <span>draft</span></button>
+            </>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-hero-go-link""#));
+        assert!(out.contains(r#"data-ui-id="ui-hero-go-button""#));
+
+        let config = PluginConfig {
+            dedupe_nested_interactive: true,
+            ..Default::default()
+        };
+        let out = transform(config.clone(), src);
+        assert!(out.contains(r#"data-ui-id="ui-hero-go-link""#));
+        assert!(!out.contains("go-button"));
+        assert!(out.contains(r#"data-ui-id="ui-hero-read-the-docs-link""#));
+        assert!(!out.contains("ui-hero-docs-button"));
+        assert!(out.contains(r#"data-ui-id="ui-hero-save-draft-button""#));
+
+        // The same holds when parents are processed first
+        let out = transform(
+            PluginConfig {
+                traversal_order: TraversalOrder::TopDown,
+                ..config
+            },
+            src,
+        );
+        assert!(out.contains(r#"data-ui-id="ui-hero-go-link""#));
+        assert!(!out.contains("go-button"));
+    }
+
+    #[test]
+    fn test_unmatched_component_filters() {
+        let mut module = parse(
+            r#"function LoginForm() { return <button>Sign In</button>; }
+            const Header = () => <a href="/">Home</a>;"#,
+        );
+        let config = PluginConfig {
+            only_in_components: vec!["LoginFrom".into(), "LoginForm".into()],
+            skip_in_components: vec!["Header:a".into(), "Footer".into()],
+            ..Default::default()
+        };
+        let mut visitor = UIBridgeVisitor::new(config, "Test.tsx".into());
+        module.visit_mut_with(&mut visitor);
+
+        assert_eq!(
+            visitor.unmatched_component_filters(),
+            [
+                r#"onlyInComponents entry "LoginFrom" matched no component (it may be defined in another file)"#,
+                r#"skipInComponents entry "Footer" matched no component (it may be defined in another file)"#,
+            ]
+        );
+        // Only logged in verbose mode, never reported as diagnostics
+        assert!(visitor.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_spread_child_diagnostic() {
+        let mut module = parse(
+            r#"function List({ items }) {
+                return <>
+                    <button>{...items}</button>
+                    <button>Save</button>
+                </>;
+            }"#,
+        );
+        let mut visitor = UIBridgeVisitor::new(PluginConfig::default(), "Test.tsx".into());
+        module.visit_mut_with(&mut visitor);
+
+        assert_eq!(
+            visitor.diagnostics(),
+            [
+                r#"<button> "ui-list-button" is text-unresolvable: a spread child ({...}) prevents text extraction"#
+            ]
+        );
+    }
+
+    #[test]
+    fn test_anchor_href() {
+        let src = r#"function Nav() {
+            return <>
+                <a href="/home">Home</a>
+                <a onClick={open}>Open</a>
+            </>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-nav-home-link" data-ui-type="link""#));
+        assert!(out.contains(r#"data-ui-id="ui-nav-open-link" data-ui-type="text""#));
+
+        let config = PluginConfig {
+            skip_anchors_without_href: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-nav-home-link" data-ui-type="link""#));
+        assert!(out.contains("<a onClick={open}>Open</a>"));
+    }
+
+    #[test]
+    fn test_descriptor_sources_by_tag() {
+        let src = r#"function Account() {
+            return <form>
+                <input name="email" placeholder="Your email" />
+                <a href="/account/billing?tab=1"><Icon /></a>
+            </form>;
+        }"#;
+        let (config, warnings) = crate::config::resolve_config(
+            r#"{ "descriptorSourcesByTag": { "input": ["name", "placeholder"], "a": ["text", "href"] } }"#,
+        );
+        assert!(warnings.is_empty());
+
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-account-email-input""#));
+        assert!(out.contains(r#"data-ui-id="ui-account-billing-link""#));
+    }
+
+    #[test]
+    fn test_radio_group() {
+        let src = r#"function Plan() {
+            return <form>
+                <input type="radio" name="plan" value="free" />
+                <input type="radio" name="plan" value="pro" />
+                <input type="radio" name="plan" value="team" />
+            </form>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        for value in ["free", "pro", "team"] {
+            let id = format!(r#"data-ui-id="ui-plan-{}-input""#, value);
+            assert!(out.contains(&id), "{}", out);
+        }
+        assert_eq!(out.matches(r#"data-ui-group="plan""#).count(), 3);
+        // Distinct values need no collision index
+        assert!(!out.contains("-input-"));
+    }
+
+    #[test]
+    fn test_describe_button_type() {
+        let src = r#"function Signup() {
+            return <form>
+                <button type="submit" />
+                <button type="reset"></button>
+                <button type="submit">Join</button>
+            </form>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(
+            r#"<button type="submit" data-ui-id="ui-signup-button" data-ui-type="button""#
+        ));
+
+        let config = PluginConfig {
+            describe_button_type: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(
+            r#"<button type="submit" data-ui-id="ui-signup-submit-button" data-ui-type="submit-button""#
+        ));
+        assert!(out.contains(
+            r#"<button type="reset" data-ui-id="ui-signup-reset-button" data-ui-type="reset-button""#
+        ));
+        // Buttons with text keep their text and type
+        assert!(out.contains(
+            r#"<button type="submit" data-ui-id="ui-signup-join-button" data-ui-type="button""#
+        ));
+    }
+
+    #[test]
+    fn test_detect_triggers() {
+        let src = r#"function Header() {
+            return <nav>
+                <button aria-haspopup="menu">Account</button>
+                <button aria-controls="faq-1">Shipping</button>
+            </nav>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains("menu-trigger") && !out.contains("disclosure"));
+
+        let config = PluginConfig {
+            detect_triggers: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(
+            out.contains(r#"data-ui-id="ui-header-account-button" data-ui-type="menu-trigger""#)
+        );
+        assert!(out.contains(r#"data-ui-id="ui-header-shipping-button" data-ui-type="disclosure""#));
+    }
+
+    #[test]
+    fn test_max_aliases_bytes() {
+        let src = r#"function Auth() {
+            return <>
+                <button>Sign in</button>
+                <button>Reset your forgotten account password</button>
+            </>;
+        }"#;
+        let config = PluginConfig {
+            max_aliases_bytes: Some(20),
+            ..Default::default()
+        };
+
+        let out = transform(config, src);
+        assert!(
+            out.contains(r#"data-ui-aliases="sign in,signin""#),
+            "{}",
+            out
+        );
+        // Nothing fits, so the attribute is left out
+        assert!(out.contains(
+            r#"data-ui-id="ui-auth-reset-your-forgotten-account-button" data-ui-type="button">"#
+        ));
+    }
+
+    #[test]
+    fn test_alias_excludes_id_descriptor() {
+        let src = "function Login() { return <button>Sign in</button>; }";
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-aliases="sign in,"#));
+
+        let config = PluginConfig {
+            alias_excludes_id_descriptor: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-login-sign-in-button""#));
+        let aliases = out.split(r#"data-ui-aliases=""#).nth(1).unwrap();
+        let aliases: Vec<&str> = aliases[..aliases.find('"').unwrap()].split(',').collect();
+        assert!(!aliases.contains(&"sign in"), "{:?}", aliases);
+        assert!(aliases.contains(&"signin") && aliases.contains(&"log in"));
+    }
+
+    #[test]
+    fn test_input_mode_type() {
+        let src = r#"function Checkout() {
+            return <form>
+                <input inputMode="numeric" placeholder="Card number" />
+                <input inputmode="tel" />
+                <input type="email" inputMode="numeric" />
+            </form>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out
+            .contains(r#"data-ui-id="ui-checkout-card-number-input" data-ui-type="number-input""#));
+        assert!(out.contains(
+            r#"<input inputmode="tel" data-ui-id="ui-checkout-input" data-ui-type="phone-input""#
+        ));
+        assert!(out.contains(r#"<input type="email" inputMode="numeric" data-ui-id="ui-checkout-input-3" data-ui-type="email-input""#));
+    }
+
+    #[test]
+    fn test_emitted_ids() {
+        let src = r#"function Toolbar() {
+            return <>
+                <button>Save</button>
+                <button>Save</button>
+                <button data-ui-ignore="id">Undo</button>
+            </>;
+        }"#;
+
+        let mut module = parse(src);
+        let mut visitor = UIBridgeVisitor::new(PluginConfig::default(), "Test.tsx".into());
+        module.visit_mut_with(&mut visitor);
+        assert_eq!(
+            visitor.emitted_ids(),
+            ["ui-toolbar-save-button", "ui-toolbar-save-button-2"]
+        );
+    }
+
+    #[test]
+    fn test_progress_element() {
+        let src = r#"function Upload({ done }) {
+            return <progress aria-label="Upload progress" value={done} max="100">{done}%</progress>;
+        }"#;
+
+        // Not instrumented by default
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains("data-ui-id"));
+
+        let config = PluginConfig {
+            additional_elements: vec!["progress".into()],
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(
+            r#"data-ui-id="ui-upload-upload-progress-progressbar" data-ui-type="progressbar""#
+        ));
+    }
+
+    #[test]
+    fn test_image_alt() {
+        let src = r#"function Header() {
+            return <nav>
+                <img src="/logo.svg" alt="Logo" title="Home" />
+                <img src="/divider.svg" alt="" title="Divider" />
+                <map><area href="/help" alt="Help center" /></map>
+            </nav>;
+        }"#;
+        let config = PluginConfig {
+            additional_elements: vec!["img".into(), "area".into()],
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-header-logo-image" data-ui-type="image""#));
+        assert!(out.contains(r#""ui-header-help-center-area""#));
+        // A decorative image's empty alt doesn't describe it
+        assert!(out.contains(r#""ui-header-divider-image""#));
+    }
+
+    #[test]
+    fn test_match_selectors() {
+        let src = r#"function Hero() {
+            return <>
+                <button className="btn btn-primary">Buy</button>
+                <button className="btn">Later</button>
+                <a href="/pricing" data-cta>Pricing</a>
+                <a href="/docs">Docs</a>
+            </>;
+        }"#;
+        let config = PluginConfig {
+            match_selectors: vec!["button.btn-primary".into(), "[data-cta]".into()],
+            ..Default::default()
+        };
+
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-hero-buy-button""#));
+        assert!(out.contains(r#"data-ui-id="ui-hero-pricing-link""#));
+        assert!(out.contains(r#"<button className="btn">Later</button>"#));
+        assert!(out.contains(r#"<a href="/docs">Docs</a>"#));
+    }
+
+    #[test]
+    fn test_id_and_word_separators() {
+        let config = PluginConfig {
+            word_separator: "_".into(),
+            ..Default::default()
+        };
+        let out = transform(
+            config,
+            "function LoginForm() { return <><button>Sign In</button><button>Sign In</button></>; }",
+        );
+        assert!(out.contains(r#"data-ui-id="ui-login_form-sign_in-button""#));
+        assert!(out.contains(r#"data-ui-id="ui-login_form-sign_in-button-2""#));
+    }
+
+    #[test]
+    fn test_describedby_aliases() {
+        // The description may come after the element it describes
+        let src = r#"function Checkout() {
+            return <form>
+                <button aria-describedby="buy-help">Buy</button>
+                <p id="buy-help">Completes your purchase</p>
+            </form>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-aliases="buy""#));
+
+        let config = PluginConfig {
+            describedby_aliases: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-checkout-buy-button""#));
+        assert!(
+            out.contains(r#"data-ui-aliases="buy,completes,purchase""#),
+            "{}",
+            out
+        );
+    }
+
+    #[test]
+    fn test_wrapping_label() {
+        let src = r#"function Signup() {
+            return <form>
+                <label>Email <input type="email" /></label>
+                <label>
+                    <span>Country</span>
+                    <select><option>Canada</option></select>
+                </label>
+                <label><input type="checkbox" placeholder="Ignored" /> Remember me</label>
+                <label>{t("name")} <input /></label>
+            </form>;
+        }"#;
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-signup-email-input""#));
+        assert!(out.contains(r#"data-ui-id="ui-signup-country-dropdown""#));
+        assert!(out.contains(r#"data-ui-id="ui-signup-remember-me-input""#));
+        // A label without static text leaves the input to its other descriptors
+        assert!(out.contains(r#"data-ui-id="ui-signup-input""#));
+    }
+
+    #[test]
+    fn test_captioned_containers() {
+        let src = r#"function Report() {
+            return <div>
+                <table>
+                    <caption>Monthly sales</caption>
+                    <tbody><tr><td>March</td><td>1200</td></tr></tbody>
+                </table>
+                <figure>
+                    <img src="chart.png" />
+                    <figcaption>Revenue by region</figcaption>
+                </figure>
+                <table><tbody><tr><td>Unlabelled</td></tr></tbody></table>
+            </div>;
+        }"#;
+        let config = PluginConfig {
+            additional_elements: vec!["table".into(), "figure".into()],
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(
+            r#"data-ui-id="ui-report-monthly-sales-table" data-ui-type="table" data-ui-aliases="monthly sales"#
+        ));
+        assert!(out
+            .contains(r#"data-ui-id="ui-report-revenue-by-region-figure" data-ui-type="figure""#));
+        // Cell text doesn't describe the table
+        assert!(out.contains(r#"data-ui-id="ui-report-table""#));
+        assert!(!out.contains("march"));
+    }
+
+    #[test]
+    fn test_no_duplicate_attributes() {
+        let src = r#"function Editor() {
+            return <button data-ui-type="primary-action">Save</button>;
+        }"#;
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-editor-save-button""#));
+        assert_eq!(out.matches("data-ui-type=").count(), 1);
+        assert!(out.contains(r#"data-ui-type="primary-action""#));
+        assert!(out.contains("data-ui-aliases="));
+    }
+
+    #[test]
+    fn test_max_injected_attributes() {
+        let src = r#"function Editor() {
+            return <button className="save" onClick={save}>Save</button>;
+        }"#;
+        let config = PluginConfig {
+            max_injected_attributes: Some(4),
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        // Injected in order until the element holds four attributes
+        assert!(out.contains(r#"data-ui-id="ui-editor-save-button" data-ui-type="button">"#));
+        assert!(!out.contains("data-ui-aliases"));
+
+        let config = PluginConfig {
+            max_injected_attributes: Some(2),
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(!out.contains("data-ui-"));
+    }
+
+    #[test]
+    fn test_ignore_attributes_for_inference() {
+        let src = r#"function Signup() {
+            return <form>
+                <input forwardedAs="textarea" name="sc-1x2y3z" type="email" />
+                <button as="a" title="sc-a1b2c3">{label}</button>
+            </form>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-type="email-input" data-ui-aliases="sc1x2y3z""#));
+        assert!(out.contains(r#"data-ui-id="ui-signup-sc-a1b2c3-button""#));
+
+        let config = PluginConfig {
+            ignore_attributes_for_inference: vec![
+                "as".into(),
+                "forwardedAs".into(),
+                "name".into(),
+                "title".into(),
+            ],
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-signup-input" data-ui-type="email-input""#));
+        assert!(out.contains(r#"data-ui-id="ui-signup-button" data-ui-type="button""#));
+        assert!(!out.contains("data-ui-aliases"));
+        // The attributes themselves are kept
+        assert!(out.contains(r#"name="sc-1x2y3z""#));
+        assert!(out.contains(r#"as="a""#));
+
+        // Nor are they inherited from ancestors
+        let src = r#"function Toolbar() {
+            return <div title="sc-d4e5f6"><button>{label}</button></div>;
+        }"#;
+        let config = PluginConfig {
+            inherit_ancestor_title: true,
+            ignore_attributes_for_inference: vec!["title".into()],
+            ..Default::default()
+        };
+        assert!(transform(config.clone(), src).contains(r#"data-ui-id="ui-toolbar-button""#));
+        let config = PluginConfig {
+            ignore_attributes_for_inference: vec![],
+            ..config
+        };
+        assert!(transform(config, src).contains(r#"data-ui-id="ui-toolbar-sc-d4e5f6-button""#));
+    }
+
+    #[test]
+    fn test_portals() {
+        let src = r#"function Modal({ onClose }) {
+            return <div>
+                <a href="/help">Help</a>
+                {ReactDOM.createPortal(
+                    <div className="modal"><button onClick={onClose}>Close</button></div>,
+                    document.body
+                )}
+                {createPortal(<input placeholder="Search" />, document.body)}
+            </div>;
+        }"#;
+
+        // Portal children are instrumented like any other JSX
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-modal-close-button""#));
+        assert!(out.contains(r#"data-ui-id="ui-modal-search-input""#));
+        assert!(!out.contains("data-ui-portal"));
+
+        let config = PluginConfig {
+            portal_component_hint: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(
+            r#"data-ui-id="ui-modal-close-button" data-ui-type="button" data-ui-portal="true""#
+        ));
+        assert!(out.contains(r#"data-ui-id="ui-modal-search-input" data-ui-type="search-input" data-ui-portal="true""#));
+        assert_eq!(out.matches("data-ui-portal").count(), 2);
+    }
+
+    #[test]
+    fn test_text_attribute() {
+        let src = r#"function Cart({ label }) {
+            return <form>
+                <button>Sign In</button>
+                <button>
+                    Add   to
+                    <b>cart</b>
+                </button>
+                <button>{label}</button>
+                <input placeholder="Coupon" />
+                <label>Gift wrap <input type="checkbox" /></label>
+            </form>;
+        }"#;
+        let config = PluginConfig {
+            emit_text_attribute: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-text="Sign In""#));
+        assert!(out.contains(r#"data-ui-id="ui-cart-add-to-cart-button""#));
+        assert!(out.contains(r#"data-ui-text="Add to cart""#));
+        assert!(out.contains(r#"data-ui-text="Sign In Add to cart Gift wrap""#));
+        // A wrapped control carries its label's text
+        assert!(out.contains(
+            r#"data-ui-id="ui-cart-gift-wrap-input" data-ui-type="checkbox" data-ui-text="Gift wrap""#
+        ));
+        // Nothing extracted from the dynamic button or the input, so nothing emitted
+        assert_eq!(out.matches("data-ui-text=").count(), 4, "{}", out);
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains("data-ui-text"));
+    }
+
+    #[test]
+    fn test_duplicate_ids() {
+        // Pre-instrumented elements are skipped, so collision handling never sees them
+        let src = r#"function Editor() {
+            return <div>
+                <button data-ui-id="ui-editor-save-button">Save</button>
+                <button data-ui-id="ui-editor-save-button">Save</button>
+                <a href="/">Home</a>
+            </div>;
+        }"#;
+        let mut program = Program::Module(parse(src));
+        program.visit_mut_with(&mut UIBridgeVisitor::new(
+            PluginConfig::default(),
+            "Test.tsx".into(),
+        ));
+        assert_eq!(
+            duplicate_ids(&PluginConfig::default(), &program),
+            [r#""ui-editor-save-button" (2 times)"#]
+        );
+
+        // Compact mode reads the ID part of the combined attribute
+        let config = PluginConfig {
+            emit_mode: EmitMode::Compact,
+            ..Default::default()
+        };
+        let mut program = Program::Module(parse(
+            r#"function Editor() { return <div><a data-ui="x|link|" /><a data-ui="x|link|home" /></div>; }"#,
+        ));
+        program.visit_mut_with(&mut UIBridgeVisitor::new(config.clone(), "Test.tsx".into()));
+        assert_eq!(duplicate_ids(&config, &program), [r#""x" (2 times)"#]);
+    }
+
+    #[test]
+    fn test_use_existing_id_verbatim() {
+        let src = r#"function Signup() {
+            return <form>
+                <input id="email" />
+                <input placeholder="Name" />
+                <button id="">Join</button>
+            </form>;
+        }"#;
+
+        // Composed from the id by default
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-signup-email-input""#));
+
+        let config = PluginConfig {
+            use_existing_id_verbatim: true,
+            ..Default::default()
+        };
+        let out = transform(config.clone(), src);
+        assert!(out.contains(r#"<input id="email" data-ui-id="email""#));
+        // Elements without a usable id are unaffected
+        assert!(out.contains(r#"data-ui-id="ui-signup-name-input""#));
+        assert!(out.contains(r#"data-ui-id="ui-signup-join-button""#));
+
+        let config = PluginConfig {
+            prefix_verbatim_ids: true,
+            ..config
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"<input id="email" data-ui-id="ui-email""#));
+    }
+
+    #[test]
+    fn test_attribute_order_invariance() {
+        /// The injected `data-ui-*` attributes of the transformed output
+        fn injected(out: &str) -> Vec<&str> {
+            out.match_indices("data-ui-")
+                .map(|(start, _)| {
+                    let attr = &out[start..];
+                    let open = attr.find('"').unwrap() + 1;
+                    &attr[..=open + attr[open..].find('"').unwrap()]
+                })
+                .collect()
+        }
+
+        /// Every ordering of `items`
+        fn permutations(items: &[&'static str]) -> Vec<Vec<&'static str>> {
+            if items.len() <= 1 {
+                return vec![items.to_vec()];
+            }
+            (0..items.len())
+                .flat_map(|i| {
+                    let mut rest = items.to_vec();
+                    let first = rest.remove(i);
+                    permutations(&rest).into_iter().map(move |mut tail| {
+                        tail.insert(0, first);
+                        tail
+                    })
+                })
+                .collect()
+        }
+
+        let config = PluginConfig {
+            emit_role_attribute: true,
+            ..Default::default()
+        };
+        let attrs = [
+            r#"type="email""#,
+            r#"aria-label="Work email""#,
+            r#"placeholder="you@example.com""#,
+            r#"title="Email""#,
+            r#"name="email""#,
+            "required",
+        ];
+        let mut expected = None;
+        for attrs in permutations(&attrs) {
+            let src = format!(
+                "function Signup() {{ return <form><input {} /><button>Join</button></form>; }}",
+                attrs.join(" ")
+            );
+            let out = transform(config.clone(), &src);
+            let attributes: Vec<String> = injected(&out).into_iter().map(String::from).collect();
+            match &expected {
+                None => expected = Some(attributes),
+                Some(expected) => assert_eq!(&attributes, expected, "{}", src),
+            }
+        }
+        let expected = expected.unwrap();
+        assert!(expected.contains(&r#"data-ui-id="ui-signup-work-email-input""#.to_string()));
+    }
+
+    #[test]
+    fn test_component_scope() {
+        let editor = r#"function Editor() {
+            return <div><button>Save</button><button>Save</button></div>;
+        }"#;
+        let toolbar = r#"function Toolbar() {
+            return <div><button>Undo</button><button>Save</button><button>Save</button></div>;
+        }"#;
+
+        // Indices count from the start of each component
+        let out = transform(PluginConfig::default(), &format!("{}\n{}", editor, toolbar));
+        assert!(out.contains(r#""ui-editor-save-button-2""#));
+        assert!(out.contains(r#""ui-toolbar-save-button-3""#));
+
+        // Adding a component doesn't shift the IDs of the ones after it
+        let extra = r#"const Header = () => <nav><button>Save</button><div /></nav>;"#;
+        let with_extra = transform(
+            PluginConfig::default(),
+            &format!("{}\n{}\n{}", extra, editor, toolbar),
+        );
+        for id in ["ui-editor-save-button-2", "ui-toolbar-save-button-3"] {
+            assert!(with_extra.contains(id), "{}", id);
+        }
+
+        // A nested component doesn't advance its parent's indices, unless it's past the
+        // maximum depth and counted as part of its parent
+        let out = transform(
+            PluginConfig::default(),
+            r#"function Editor() {
+                const Inner = () => <div><button>Save</button><button>Save</button></div>;
+                return <div><button>Save</button><button>Save</button></div>;
+            }"#,
+        );
+        assert!(out.contains(r#""ui-inner-save-button-2""#));
+        assert!(out.contains(r#""ui-editor-save-button-2""#));
+        let config = PluginConfig {
+            max_component_depth: Some(1),
+            ..Default::default()
+        };
+        let out = transform(
+            config,
+            r#"function Editor() {
+                const Inner = () => <div><button>Save</button><button>Save</button></div>;
+                return <div><button>Save</button><button>Save</button></div>;
+            }"#,
+        );
+        assert!(!out.contains("ui-inner"));
+        assert!(out.contains(r#""ui-editor-save-button-4""#));
+
+        // Components sharing a name still get distinct IDs
+        let out = transform(
+            PluginConfig::default(),
+            r#"function A() { const Row = () => <div><button>Save</button><button>Save</button></div>; }
+            function B() { const Row = () => <div><button>Save</button><button>Save</button></div>; }"#,
+        );
+        assert_eq!(out.matches(r#""ui-row-save-button""#).count(), 1);
+        for id in [
+            "ui-row-save-button-1",
+            "ui-row-save-button-2",
+            "ui-row-save-button-3",
+        ] {
+            assert_eq!(out.matches(&format!("\"{}\"", id)).count(), 1, "{}", id);
+        }
+
+        // IDs without the component name count through the file
+        let config = PluginConfig {
+            include_component_name: false,
+            ..Default::default()
+        };
+        let out = transform(config, &format!("{}\n{}", editor, toolbar));
+        for id in ["ui-save-button-2", "ui-save-button-4", "ui-save-button-5"] {
+            assert!(out.contains(id), "{}", id);
+        }
+    }
+
+    #[test]
+    fn test_children_inherit_parent_component() {
+        let src = r#"function Settings() {
+            return <Card footer={<button>Cancel</button>}>
+                <button>Save</button>
+                <Card.Body><input placeholder="Name" /></Card.Body>
+            </Card>;
+        }"#;
+
+        // JSX passed to a component is named after the component it's written in
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#""ui-settings-save-button""#));
+        assert!(out.contains(r#""ui-settings-cancel-button""#));
+        assert!(out.contains(r#""ui-settings-name-input""#));
+
+        // ...or after the one it's passed to
+        let config = PluginConfig {
+            children_inherit_parent_component: false,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#""ui-card-save-button""#));
+        assert!(out.contains(r#""ui-card-cancel-button""#));
+        assert!(out.contains(r#""ui-card-body-name-input""#));
+
+        // Component filters follow the same rule
+        let config = PluginConfig {
+            children_inherit_parent_component: false,
+            skip_in_components: vec!["CardBody".into()],
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#""ui-card-save-button""#));
+        assert!(!out.contains("name-input"));
+    }
+
+    #[test]
+    fn test_mirror_to_id() {
+        let src = r#"function Login(props) {
+            return <form>
+                <button>Sign In</button>
+                <button id="forgot">Forgot Password</button>
+                <input {...props} />
+            </form>;
+        }"#;
+        let config = PluginConfig {
+            mirror_to_id: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(
+            r#"<button id="ui-login-sign-in-button" data-ui-id="ui-login-sign-in-button""#
+        ));
+        // An existing id is kept
+        assert!(out.contains(r#"<button id="forgot" data-ui-id="ui-login-forgot-button""#));
+        assert_eq!(out.matches("id=\"forgot\"").count(), 1);
+        // A spread's id takes precedence over the mirrored one
+        assert!(
+            out.contains(r#"<input id="ui-login-input" {...props}"#),
+            "{}",
+            out
+        );
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains(r#" id="ui-"#));
+    }
+
+    #[test]
+    fn test_mirror_to_id_list_items() {
+        let src = r#"function Rows({ rows }) {
+            return <ul>
+                {rows.map((row, i) => <li>
+                    <button>Edit</button>
+                    {row.tags.map((tag, j) => <a href="/tag">Tag</a>)}
+                </li>)}
+                {rows.map((row) => <input />)}
+            </ul>;
+        }"#;
+        let config = PluginConfig {
+            mirror_to_id: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        // Items are told apart by their indices
+        assert!(out.contains(
+            r#"<button id={"ui-rows-edit-button-" + i} data-ui-id="ui-rows-edit-button""#
+        ));
+        assert!(out.contains(r#"<a id={"ui-rows-tag-link-" + i + "-" + j} href="/tag""#));
+        // Without an index, items would share an `id`
+        assert!(out.contains(r#"<input data-ui-id="ui-rows-input""#));
+    }
+
+    #[test]
+    fn test_classname_type_map() {
+        let src = r#"function Nav() {
+            return <nav>
+                <a className="nav-link active" href="/home">Home</a>
+                <a className={`nav-link`} href="/about">About</a>
+                <button className="btn btn-primary" type="submit" />
+                <button className={styles.btn}>Menu</button>
+            </nav>;
+        }"#;
+        let config = PluginConfig {
+            classname_type_map: HashMap::from([
+                ("nav-link".to_string(), "nav-link".to_string()),
+                ("btn-primary".to_string(), "primary-button".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert_eq!(out.matches(r#"data-ui-type="nav-link""#).count(), 2);
+        // Overrides the type described by `type="submit"`
+        assert!(out.contains(r#"data-ui-type="primary-button""#));
+        assert!(!out.contains(r#"data-ui-type="submit-button""#));
+        // Dynamic classNames keep the tag-based type
+        assert!(out.contains(r#"data-ui-type="button""#));
+
+        let out = transform(PluginConfig::default(), src);
+        assert_eq!(out.matches(r#"data-ui-type="link""#).count(), 2);
+    }
+
+    #[test]
+    fn test_reserved_ids() {
+        let src = r#"function Editor() {
+            return <div>
+                <button>Save</button>
+                <button>Cancel</button>
+            </div>;
+        }"#;
+        let config = PluginConfig {
+            reserved_ids: vec!["ui-editor-save-button".into()],
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(!out.contains(r#""ui-editor-save-button""#));
+        assert!(out.contains(r#""ui-editor-save-button-1""#));
+        assert!(out.contains(r#""ui-editor-cancel-button""#));
+    }
+
+    #[test]
+    fn test_index_base_and_pad_width() {
+        let src = r#"function Editor() {
+            return <div>
+                <button>Save</button>
+                <button>Save</button>
+                <button>Save</button>
+            </div>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#""ui-editor-save-button-2""#));
+        assert!(out.contains(r#""ui-editor-save-button-3""#));
+
+        let config = PluginConfig {
+            index_base: 0,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#""ui-editor-save-button-1""#));
+        assert!(out.contains(r#""ui-editor-save-button-2""#));
+        assert!(!out.contains(r#""ui-editor-save-button-3""#));
+
+        let config = PluginConfig {
+            index_pad_width: 2,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#""ui-editor-save-button-02""#));
+        assert!(out.contains(r#""ui-editor-save-button-03""#));
+    }
+
+    #[test]
+    fn test_skip_fully_dynamic() {
+        let src = r#"function Toolbar({ label, count }) {
+            return <div>
+                <button onClick={refresh} aria-label={label}>{count}</button>
+                <button aria-label={label} title="Refresh">{count}</button>
+                <a href={url}>{label}</a>
+            </div>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-toolbar-button""#));
+        assert!(out.contains(r#"data-ui-id="ui-toolbar-link""#));
+
+        let config = PluginConfig {
+            skip_fully_dynamic: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        assert!(!out.contains(r#"data-ui-id="ui-toolbar-button""#));
+        assert!(!out.contains(r#"data-ui-id="ui-toolbar-link""#));
+        // The static title still describes it
+        assert!(out.contains(r#"data-ui-id="ui-toolbar-refresh-button""#));
+        assert_eq!(out.matches("data-ui-id").count(), 1);
+    }
+
+    #[test]
+    fn test_skip_conditional() {
+        let src = r#"function Editor({ dirty, saving }) {
+            return <div>
+                {dirty && <button>Save</button>}
+                {saving ? <span><a href="/status">Status</a></span> : <button>Publish</button>}
+                {dirty || <input placeholder="Title" />}
+                <button>Close</button>
+            </div>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        for id in [
+            "save-button",
+            "status-link",
+            "publish-button",
+            "title-input",
+        ] {
+            assert!(out.contains(&format!("ui-editor-{}", id)), "{}", id);
+        }
+
+        let config = PluginConfig {
+            skip_conditional: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        for id in [
+            "save-button",
+            "status-link",
+            "publish-button",
+            "title-input",
+        ] {
+            assert!(!out.contains(&format!("ui-editor-{}", id)), "{}", id);
+        }
+        assert!(out.contains(r#"data-ui-id="ui-editor-close-button""#));
+    }
+
+    #[test]
+    fn test_separate_id_and_alias_sources() {
+        let src = r#"function Signup() {
+            return <form>
+                <input placeholder="Enter the email you signed up with" name="email" />
+                <button>Join</button>
+            </form>;
+        }"#;
+        let config = PluginConfig {
+            id_sources: Some(vec![DescriptorSource::Text, DescriptorSource::Name]),
+            alias_sources: vec![DescriptorSource::Text, DescriptorSource::Placeholder],
+            ..Default::default()
+        };
+
+        let out = transform(config, src);
+        // The placeholder only feeds the aliases, the name only the ID
+        assert!(out.contains(
+            r#"data-ui-id="ui-signup-email-input" data-ui-type="email-input" data-ui-aliases="enter the email you signed up with""#
+        ));
+        // The text feeds both
+        assert!(out.contains(r#"data-ui-id="ui-signup-join-button""#));
+        assert!(out.contains(r#"data-ui-aliases="join"#));
+    }
+
+    #[test]
+    fn test_legacy_babel_compat() {
+        let src = r#"function LoginForm() {
+            return <form>
+                <input placeholder="Enter email" name="user-email" />
+                <button>Save</button>
+                <button>Save</button>
+                <a href="/help">Help <span>center</span></a>
+            </form>;
+        }"#;
+        let config = PluginConfig {
+            legacy_babel_compat: true,
+            ..Default::default()
+        };
+        let out = transform(config, src);
+        // Outputs of the Babel plugin for the same source
+        for expected in [
+            r#"<form data-ui-id="ui-loginform-form" data-ui-type="form">"#,
+            r#"data-ui-id="ui-loginform-enter-email-input" data-ui-type="input" data-ui-aliases="enter email,useremail,input""#,
+            r#"data-ui-id="ui-loginform-save-submit-button" data-ui-type="submit-button" data-ui-aliases="save,store,keep,preserve,button""#,
+            r#"data-ui-id="ui-loginform-save-submit-button-1""#,
+            r#"data-ui-id="ui-loginform-help-link" data-ui-type="link" data-ui-aliases="help,support,info,about,link""#,
+        ] {
+            assert!(out.contains(expected), "{}", expected);
+        }
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains("ui-login-form-save-button-2"));
+    }
+
+    #[test]
+    fn test_skip_svg() {
+        let src = r#"function Chart() {
+            return <div>
+                <svg>
+                    <a href="/q1"><text>Q1</text></a>
+                    <circle onClick={select} aria-label="Peak" />
+                    <foreignObject><button>Zoom</button></foreignObject>
+                </svg>
+                <a href="/report">Report</a>
+            </div>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(out.contains(r#"data-ui-id="ui-chart-q1-link""#));
+        assert!(out.contains(r#"data-ui-id="ui-chart-zoom-button""#));
+
+        let config = PluginConfig {
+            skip_svg: true,
+            ..Default::default()
+        };
+        let out = transform(config.clone(), src);
+        assert!(!out.contains("ui-chart-q1-link"));
+        assert!(!out.contains("ui-chart-zoom-button"));
+        assert!(out.contains(r#"data-ui-id="ui-chart-report-link""#));
+
+        // Allowlisted tags are instrumented inside the <svg>, even when not in `elements`
+        let config = PluginConfig {
+            svg_instrument_elements: vec!["a".into(), "circle".into()],
+            ..config
+        };
+        let out = transform(config, src);
+        assert!(out.contains(r#"data-ui-id="ui-chart-q1-link""#));
+        assert!(out.contains(r#"data-ui-id="ui-chart-peak-circle""#));
+        assert!(!out.contains("ui-chart-zoom-button"));
+    }
+
+    #[test]
+    fn test_scope_attribute() {
+        let src = r#"function Page() {
+            return <>
+                <button>Vendor chat</button>
+                <form data-ui-scope>
+                    <input placeholder="Email" />
+                    <div><button>Subscribe</button></div>
+                </form>
+                <a href="/legal">Legal</a>
+            </>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert_eq!(out.matches("data-ui-id=").count(), 5);
+
+        for order in [TraversalOrder::BottomUp, TraversalOrder::TopDown] {
+            let config = PluginConfig {
+                scope_attribute: Some("data-ui-scope".into()),
+                traversal_order: order,
+                ..Default::default()
+            };
+            let out = transform(config, src);
+            assert!(out.contains(r#"data-ui-id="ui-page-email-input""#));
+            assert!(out.contains(r#"data-ui-id="ui-page-subscribe-button""#));
+            // Neither the scope element itself nor anything outside it
+            assert!(out.contains("<form data-ui-scope>"));
+            assert!(out.contains("<button>Vendor chat</button>"));
+            assert!(out.contains(r#"<a href="/legal">Legal</a>"#));
+        }
+    }
+
+    #[test]
+    fn test_guarantee_global_unique() {
+        let src = r#"function LoginForm() {
+            return <form><button>Sign In</button><button>Sign In</button></form>;
+        }"#;
+        let transform_file = |config: &PluginConfig, filename: &str| {
+            let mut module = parse(src);
+            let mut visitor = UIBridgeVisitor::new(config.clone(), filename.into());
+            module.visit_mut_with(&mut visitor);
+            visitor.emitted_ids().to_vec()
+        };
+
+        // Each file is unique on its own, but not together
+        let config = PluginConfig::default();
+        let a = transform_file(&config, "/app/admin/LoginForm.tsx");
+        let b = transform_file(&config, "/app/shop/LoginForm.tsx");
+        assert_eq!(a, b);
+
+        // Salting leaves the readable IDs alone and makes the collision suffixes unique
+        let config = PluginConfig {
+            guarantee_global_unique: true,
+            ..Default::default()
+        };
+        let a = transform_file(&config, "admin/LoginForm.tsx");
+        let b = transform_file(&config, "shop/LoginForm.tsx");
+        assert_eq!(a[0], "ui-login-form-sign-in-button");
+        assert_eq!(a[0], b[0]);
+        assert_eq!(
+            a[1],
+            format!(
+                "ui-login-form-sign-in-button-2-{}",
+                file_salt("admin/LoginForm.tsx")
+            )
+        );
+        assert_ne!(a[1], b[1]);
+
+        // Hashed IDs are all salted
+        let config = PluginConfig {
+            hash_ids: true,
+            ..config
+        };
+        let a = transform_file(&config, "admin/LoginForm.tsx");
+        let b = transform_file(&config, "shop/LoginForm.tsx");
+        let all: HashSet<&String> = a.iter().chain(&b).collect();
+        assert_eq!(all.len(), 6, "{:?} {:?}", a, b);
+    }
+
+    #[test]
+    fn test_options_and_optgroups() {
+        let src = r#"function Region() {
+            return <select name="region">
+                <optgroup label="Europe">
+                    <option value="de">Germany</option>
+                    <option value="fr" />
+                </optgroup>
+            </select>;
+        }"#;
+        let config = PluginConfig {
+            additional_elements: vec!["option".into(), "optgroup".into()],
+            ..Default::default()
+        };
+
+        let out = transform(config, src);
+        assert!(out.contains(
+            r#"<optgroup label="Europe" data-ui-id="ui-region-europe-group" data-ui-type="group""#
+        ));
+        assert!(out.contains(r#"data-ui-id="ui-region-germany-option" data-ui-type="option""#));
+        assert!(out.contains(
+            r#"<option value="fr" data-ui-id="ui-region-fr-option" data-ui-type="option""#
+        ));
+    }
+
+    #[test]
+    fn test_mark_primary_actions() {
+        let src = r#"function Signup() {
+            return <form>
+                <button type="button">Cancel</button>
+                <div><button type="submit">Create account</button></div>
+                <button>Later</button>
+            </form>;
+        }"#;
+
+        let out = transform(PluginConfig::default(), src);
+        assert!(!out.contains("data-ui-primary"));
+
+        let config = PluginConfig {
+            mark_primary_actions: true,
+            ..Default::default()
+        };
+        let out = transform(config.clone(), src);
+        assert_eq!(out.matches("data-ui-primary").count(), 1);
+        assert!(out.contains(
+            r#"data-ui-id="ui-signup-create-account-button" data-ui-type="button" data-ui-primary="true""#
+        ));
+
+        // Without an explicit submit, the first button without a type submits the form
+        let src = r#"function Search() {
+            return <form>
+                <input type="reset" />
+                <button>Go</button>
+                <button>Advanced</button>
+            </form>;
+        }"#;
+        let out = transform(config.clone(), src);
+        assert_eq!(out.matches("data-ui-primary").count(), 1);
+        assert!(out.contains(
+            r#"data-ui-id="ui-search-go-button" data-ui-type="button" data-ui-primary="true""#
+        ));
+
+        // Elements without a real position can't be told apart, so none is marked
+        struct ClearSpans;
+        impl VisitMut for ClearSpans {
+            fn visit_mut_span(&mut self, span: &mut Span) {
+                *span = DUMMY_SP;
+            }
+        }
+        let mut module = parse(src);
+        module.visit_mut_with(&mut ClearSpans);
+        module.visit_mut_with(&mut UIBridgeVisitor::new(config, "Test.tsx".into()));
+        let out = to_code(&module);
+        assert!(out.contains("ui-search-advanced-button"));
+        assert!(!out.contains("data-ui-primary"));
+    }
+
+    #[test]
+    fn test_html_element_predicate() {
+        let src = r#"function Widgets() {
+            return <>
+                <my-widget>Chart</my-widget>
+                <button>Save</button>
+            </>;
+        }"#;
+        let config = |html_element_predicate| PluginConfig {
+            additional_elements: vec!["my-widget".into()],
+            html_element_predicate,
+            html_elements: vec!["button".into()],
+            ..Default::default()
+        };
+
+        let out = transform(config(HtmlElementPredicate::LowercaseFirst), src);
+        assert!(out.contains(r#"data-ui-id="ui-widgets-chart-my-widget""#));
+        assert!(out.contains(r#"data-ui-id="ui-widgets-save-button""#));
+
+        for predicate in [
+            HtmlElementPredicate::ExplicitList,
+            HtmlElementPredicate::ContainsDashIsCustom,
+        ] {
+            let out = transform(config(predicate), src);
+            assert!(out.contains("<my-widget>Chart</my-widget>"));
+            assert!(out.contains(r#"data-ui-id="ui-widgets-save-button""#));
+        }
+    }
+
+    /// Time the visitor alone over a file with `count` small components
+    fn visit_components(count: usize) -> std::time::Duration {
+        let src: String = (0..count)
+            .map(|i| {
+                format!(
+                    "function Row{i}() {{ return <div><button>Edit</button><input name=\"q{i}\" /></div>; }}\n"
+                )
+            })
+            .collect();
+        let mut module = parse(&src);
+
+        let start = std::time::Instant::now();
+        module.visit_mut_with(&mut UIBridgeVisitor::new(
+            PluginConfig::default(),
+            "Test.tsx".into(),
+        ));
+        start.elapsed()
+    }
+
+    /// Benchmark guarding against per-component visitor state copies.
+    /// Run with `cargo test --release -- --ignored scaling`.
+    #[test]
+    #[ignore]
+    fn test_component_count_scaling() {
+        // Warm up allocations before measuring
+        visit_components(500);
+
+        let small = visit_components(1_000);
+        let large = visit_components(8_000);
+        let ratio = large.as_secs_f64() / small.as_secs_f64();
+
+        // 8x the components should cost roughly 8x the time, nowhere near 64x
+        assert!(
+            ratio < 20.0,
+            "visitor scaled super-linearly (ratio {ratio:.1})"
+        );
+    }
 }